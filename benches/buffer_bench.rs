@@ -31,6 +31,7 @@ fn bench_pool_vs_direct(c: &mut Criterion) {
             buffer_size: 1024,
             max_pool_size: 100,
             min_pool_size: 10,
+            ..PoolConfig::default()
         });
 
         b.iter(|| {
@@ -61,6 +62,7 @@ fn bench_fast_pool_vs_standard(c: &mut Criterion) {
             buffer_size: 1024,
             max_pool_size: 100,
             min_pool_size: 10,
+            ..PoolConfig::default()
         });
 
         b.iter(|| {
@@ -75,6 +77,7 @@ fn bench_fast_pool_vs_standard(c: &mut Criterion) {
             buffer_size: 1024,
             max_pool_size: 100,
             min_pool_size: 10,
+            ..PoolConfig::default()
         });
 
         b.iter(|| {
@@ -150,6 +153,7 @@ fn bench_packet_processing(c: &mut Criterion) {
             buffer_size: 1500,
             max_pool_size: 200,
             min_pool_size: 50,
+            ..PoolConfig::default()
         });
 
         b.iter(|| {
@@ -48,6 +48,7 @@ fn main() -> Result<()> {
         buffer_size: 4096,
         max_pool_size: 50,
         min_pool_size: 5,
+        ..PoolConfig::default()
     });
 
     println!("Pool initialized with {} buffers", pool.available());
@@ -12,6 +12,7 @@ fn main() -> Result<()> {
         buffer_size: 1500, // MTU size
         max_pool_size: 1000,
         min_pool_size: 100,
+        ..PoolConfig::default()
     });
 
     let num_packets = 10_000;
@@ -85,6 +86,7 @@ fn main() -> Result<()> {
         buffer_size: 1500,
         max_pool_size: 1000,
         min_pool_size: 100,
+        ..PoolConfig::default()
     });
 
     let start = Instant::now();
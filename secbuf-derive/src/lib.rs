@@ -0,0 +1,332 @@
+// secbuf-derive/src/lib.rs
+//! `#[derive(Encode, Decode)]` for SSH wire-format messages.
+//!
+//! Use through the `derive` feature of `secbuf`, which re-exports both
+//! macros next to the [`Encode`](../secbuf/trait.Encode.html) and
+//! [`Decode`](../secbuf/trait.Decode.html) traits.
+//!
+//! # Structs
+//!
+//! Fields are written in declaration order with their own `Encode` impl:
+//! `u8` as `byte`, `bool` as `boolean`, `u32`/`u64` as `uint32`/`uint64`,
+//! `Vec<u8>`, `String` and `SecretBytes` as `string`, and `[u8; N]` as raw
+//! `byte[n]`.  A `#[wire(...)]` attribute selects another wire type:
+//!
+//! | Attribute | Field type | Wire type |
+//! |-----------|------------|-----------|
+//! | `#[wire(mpint)]` | `SecretBytes` or `Vec<u8>` (big-endian magnitude) | `mpint` |
+//! | `#[wire(name_list)]` | `Vec<String>` | `name-list` |
+//! | `#[wire(string)]` | any `Encode`/`Decode` type | the field's encoding inside a `string` |
+//!
+//! `#[wire(string)]` is for nested structures such as public key blobs and
+//! signatures; decoding fails if the field does not fill the string exactly.
+//! Every length is checked against the SSH string limit (400 000 bytes) by
+//! the underlying `Buffer` calls.
+//!
+//! # Message enums
+//!
+//! Each variant is preceded by a discriminant byte, taken from the variant's
+//! discriminant (explicit `= N` or implicit, as in Rust).  Enums with fields
+//! need `#[repr(u8)]` for explicit discriminants.  Decoding an unknown byte
+//! returns `BufferError::InvalidData`.
+//!
+//! # Examples
+//!
+//! ```
+//! use secbuf::{Buffer, Decode, Encode, SecretBytes};
+//! # use secbuf::BufferError;
+//!
+//! #[derive(Encode, Decode)]
+//! struct KexEcdhReply {
+//!     #[wire(string)]
+//!     host_key: HostKey,
+//!     q_s: Vec<u8>,
+//!     #[wire(mpint)]
+//!     k: SecretBytes,
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct HostKey {
+//!     algorithm: String,
+//!     key: [u8; 32],
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! #[repr(u8)]
+//! enum Message {
+//!     Ignore(Vec<u8>) = 2,
+//!     ServiceRequest { service: String } = 5,
+//!     NewKeys = 21,
+//!     KexEcdhReply(KexEcdhReply) = 31,
+//! }
+//!
+//! let mut buf = Buffer::new(256);
+//! Message::ServiceRequest { service: "ssh-userauth".into() }.encode(&mut buf)?;
+//! assert_eq!(buf.as_slice(), b"\x05\0\0\0\x0cssh-userauth");
+//!
+//! buf.set_pos(0)?;
+//! assert!(matches!(Message::decode(&mut buf)?, Message::ServiceRequest { .. }));
+//! # Ok::<(), BufferError>(())
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Expr, ExprLit, Field, Fields,
+    Generics, Lit, Result,
+};
+
+/// Derives `secbuf::Encode`. See the [crate docs](crate).
+#[proc_macro_derive(Encode, attributes(wire))]
+pub fn derive_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_encode(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `secbuf::Decode`. See the [crate docs](crate).
+#[proc_macro_derive(Decode, attributes(wire))]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Wire type selected by a field's `#[wire(...)]` attribute.
+#[derive(Clone, Copy)]
+enum Wire {
+    /// The field type's own `Encode`/`Decode` impl.
+    Native,
+    Mpint,
+    NameList,
+    String,
+}
+
+impl Wire {
+    fn of(field: &Field) -> Result<Self> {
+        let mut wire = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("wire")) {
+            attr.parse_nested_meta(|meta| {
+                let kind = if meta.path.is_ident("mpint") {
+                    Wire::Mpint
+                } else if meta.path.is_ident("name_list") {
+                    Wire::NameList
+                } else if meta.path.is_ident("string") {
+                    Wire::String
+                } else {
+                    return Err(meta.error("expected `mpint`, `name_list` or `string`"));
+                };
+                if wire.replace(kind).is_some() {
+                    return Err(meta.error("only one wire type may be given per field"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(wire.unwrap_or(Wire::Native))
+    }
+
+    /// Statement writing the field behind the reference `value` to `__buf`.
+    fn encode(self, value: &TokenStream2) -> TokenStream2 {
+        match self {
+            Wire::Native => quote! { ::secbuf::Encode::encode(#value, __buf)?; },
+            Wire::Mpint => quote! { ::secbuf::__private::encode_mpint(#value, __buf)?; },
+            Wire::NameList => quote! { __buf.put_name_list(#value)?; },
+            Wire::String => quote! { ::secbuf::__private::encode_nested(#value, __buf)?; },
+        }
+    }
+
+    /// Expression reading the field from `__buf`.
+    fn decode(self) -> TokenStream2 {
+        match self {
+            Wire::Native => quote! { ::secbuf::Decode::decode(__buf)? },
+            Wire::Mpint => quote! { ::secbuf::__private::decode_mpint(__buf)? },
+            Wire::NameList => quote! { __buf.get_name_list()? },
+            Wire::String => quote! { ::secbuf::__private::decode_nested(__buf)? },
+        }
+    }
+}
+
+/// Names under which the fields of a variant are bound in a pattern.
+fn bindings(fields: &Fields) -> Vec<TokenStream2> {
+    (0..fields.len())
+        .map(|i| {
+            let ident = format_ident!("__field{}", i);
+            quote!(#ident)
+        })
+        .collect()
+}
+
+/// Pattern (or constructor, with `values`) for `path` with the given fields.
+fn shape(path: TokenStream2, fields: &Fields, values: &[TokenStream2]) -> TokenStream2 {
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| &f.ident);
+            quote! { #path { #(#names: #values),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path ( #(#values),* ) },
+        Fields::Unit => path,
+    }
+}
+
+fn encode_fields(fields: &Fields, refs: &[TokenStream2]) -> Result<TokenStream2> {
+    let mut out = TokenStream2::new();
+    for (field, value) in fields.iter().zip(refs) {
+        out.extend(Wire::of(field)?.encode(value));
+    }
+    Ok(out)
+}
+
+fn decode_fields(fields: &Fields) -> Result<Vec<TokenStream2>> {
+    fields.iter().map(|f| Wire::of(f).map(Wire::decode)).collect()
+}
+
+/// Adds `bound` to every type parameter.
+fn add_bounds(mut generics: Generics, bound: syn::Path) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+/// The discriminant byte of every variant, following Rust's rules for
+/// implicit discriminants.
+fn discriminants(data: &DataEnum) -> Result<Vec<u8>> {
+    let mut next = Some(0u8);
+    let mut tags: Vec<u8> = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let tag = match &variant.discriminant {
+            Some((
+                _,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(int), ..
+                }),
+            )) => int.base10_parse::<u8>()?,
+            Some((_, expr)) => {
+                return Err(syn::Error::new_spanned(
+                    expr,
+                    "discriminant must be an integer literal",
+                ));
+            }
+            None => next.ok_or_else(|| {
+                syn::Error::new_spanned(&variant.ident, "discriminant does not fit in a byte")
+            })?,
+        };
+        if tags.contains(&tag) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("duplicate discriminant {tag}"),
+            ));
+        }
+        tags.push(tag);
+        next = tag.checked_add(1);
+    }
+    Ok(tags)
+}
+
+fn expand_encode(input: &DeriveInput) -> Result<TokenStream2> {
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let refs: Vec<_> = data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| match &field.ident {
+                    Some(ident) => quote!(&self.#ident),
+                    None => {
+                        let index = syn::Index::from(i);
+                        quote!(&self.#index)
+                    }
+                })
+                .collect();
+            encode_fields(&data.fields, &refs)?
+        }
+        Data::Enum(data) => {
+            let tags = discriminants(data)?;
+            let mut arms = TokenStream2::new();
+            for (variant, tag) in data.variants.iter().zip(tags) {
+                let ident = &variant.ident;
+                let names = bindings(&variant.fields);
+                let pattern = shape(quote!(Self::#ident), &variant.fields, &names);
+                let fields = encode_fields(&variant.fields, &names)?;
+                arms.extend(quote! {
+                    #pattern => {
+                        __buf.put_byte(#tag)?;
+                        #fields
+                    }
+                });
+            }
+            quote! {
+                match self {
+                    #arms
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "Encode cannot be derived for unions",
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let generics = add_bounds(input.generics.clone(), parse_quote!(::secbuf::Encode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::secbuf::Encode for #name #ty_generics #where_clause {
+            fn encode(&self, __buf: &mut ::secbuf::Buffer) -> ::secbuf::Result<()> {
+                #body
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+fn expand_decode(input: &DeriveInput) -> Result<TokenStream2> {
+    let body = match &input.data {
+        Data::Struct(data) => shape(quote!(Self), &data.fields, &decode_fields(&data.fields)?),
+        Data::Enum(data) => {
+            let tags = discriminants(data)?;
+            let mut arms = TokenStream2::new();
+            for (variant, tag) in data.variants.iter().zip(tags) {
+                let ident = &variant.ident;
+                let value = shape(
+                    quote!(Self::#ident),
+                    &variant.fields,
+                    &decode_fields(&variant.fields)?,
+                );
+                arms.extend(quote! { #tag => #value, });
+            }
+            quote! {
+                match __buf.get_byte()? {
+                    #arms
+                    _ => return ::core::result::Result::Err(
+                        ::secbuf::__private::unknown_discriminant(),
+                    ),
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "Decode cannot be derived for unions",
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let generics = add_bounds(input.generics.clone(), parse_quote!(::secbuf::Decode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::secbuf::Decode for #name #ty_generics #where_clause {
+            fn decode(__buf: &mut ::secbuf::Buffer) -> ::secbuf::Result<Self> {
+                ::core::result::Result::Ok(#body)
+            }
+        }
+    })
+}
@@ -0,0 +1,8 @@
+// secbuf-derive/tests/compile_fail.rs
+//! Error paths of `#[derive(Encode, Decode)]`, checked with trybuild
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
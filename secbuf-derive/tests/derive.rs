@@ -0,0 +1,169 @@
+// secbuf-derive/tests/derive.rs
+//! Round-trip tests for `#[derive(Encode, Decode)]`
+
+use secbuf::{Buffer, BufferError, Decode, Encode, SecretBytes};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct KexInit {
+    cookie: [u8; 16],
+    #[wire(name_list)]
+    kex_algorithms: Vec<String>,
+    #[wire(name_list)]
+    server_host_key_algorithms: Vec<String>,
+    first_kex_packet_follows: bool,
+    reserved: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct PublicKey {
+    algorithm: String,
+    key: Vec<u8>,
+}
+
+#[derive(Encode, Decode)]
+struct DhReply {
+    #[wire(string)]
+    host_key: PublicKey,
+    #[wire(mpint)]
+    f: Vec<u8>,
+    #[wire(mpint)]
+    k: SecretBytes,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Wrapper<T>(T, u8);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[repr(u8)]
+enum Message {
+    Disconnect { reason: u32, description: String } = 1,
+    Ignore(Vec<u8>),
+    ServiceRequest(String) = 5,
+    NewKeys = 21,
+}
+
+fn encode<T: Encode>(value: &T) -> Buffer {
+    let mut buf = Buffer::new(512);
+    value.encode(&mut buf).unwrap();
+    buf.set_pos(0).unwrap();
+    buf
+}
+
+fn expected(build: impl FnOnce(&mut Buffer)) -> Vec<u8> {
+    let mut buf = Buffer::new(512);
+    build(&mut buf);
+    buf.as_slice().to_vec()
+}
+
+#[test]
+fn test_struct_matches_manual_encoding() {
+    let msg = KexInit {
+        cookie: [0x42; 16],
+        kex_algorithms: vec!["curve25519-sha256".into(), "ext-info-s".into()],
+        server_host_key_algorithms: vec!["ssh-ed25519".into()],
+        first_kex_packet_follows: false,
+        reserved: 0,
+    };
+    let mut buf = encode(&msg);
+    assert_eq!(
+        buf.as_slice(),
+        expected(|b| {
+            b.put_bytes(&[0x42; 16]).unwrap();
+            b.put_name_list(&["curve25519-sha256", "ext-info-s"]).unwrap();
+            b.put_name_list(&["ssh-ed25519"]).unwrap();
+            b.put_bool(false).unwrap();
+            b.put_u32(0).unwrap();
+        })
+    );
+    assert_eq!(KexInit::decode(&mut buf).unwrap(), msg);
+    assert_eq!(buf.remaining(), 0);
+}
+
+#[test]
+fn test_nested_string_and_mpint() {
+    let reply = DhReply {
+        host_key: PublicKey {
+            algorithm: "ssh-ed25519".into(),
+            key: vec![0x11; 32],
+        },
+        f: vec![0x00, 0x80],
+        k: SecretBytes::from_slice(&[0x7F, 0x01]),
+    };
+    let mut buf = encode(&reply);
+
+    let mut blob = Buffer::new(64);
+    reply.host_key.encode(&mut blob).unwrap();
+    assert_eq!(
+        buf.as_slice(),
+        expected(|b| {
+            b.put_string(blob.as_slice()).unwrap();
+            b.put_mpint(&[0x80]).unwrap();
+            b.put_mpint(&[0x7F, 0x01]).unwrap();
+        })
+    );
+
+    let decoded = DhReply::decode(&mut buf).unwrap();
+    assert_eq!(decoded.host_key, reply.host_key);
+    assert_eq!(decoded.f, [0x80]);
+    assert_eq!(decoded.k.expose_secret(), &[0x7F, 0x01]);
+}
+
+#[test]
+fn test_nested_string_must_be_filled() {
+    let mut buf = Buffer::new(64);
+    buf.put_string(b"\0\0\0\x01a\0\0\0\0extra").unwrap();
+    buf.put_mpint(&[1]).unwrap();
+    buf.put_mpint(&[2]).unwrap();
+    buf.set_pos(0).unwrap();
+    assert_eq!(
+        DhReply::decode(&mut buf).err(),
+        Some(BufferError::InvalidData("trailing data in string"))
+    );
+}
+
+#[test]
+fn test_generic_tuple_struct() {
+    let value = Wrapper(0xDEAD_BEEFu32, 7);
+    let mut buf = encode(&value);
+    assert_eq!(buf.as_slice(), &[0xDE, 0xAD, 0xBE, 0xEF, 7]);
+    assert_eq!(Wrapper::<u32>::decode(&mut buf).unwrap(), value);
+}
+
+#[test]
+fn test_enum_discriminants() {
+    let cases = [
+        (
+            Message::Disconnect {
+                reason: 11,
+                description: "bye".into(),
+            },
+            1,
+        ),
+        (Message::Ignore(b"pad".to_vec()), 2),
+        (Message::ServiceRequest("ssh-userauth".into()), 5),
+        (Message::NewKeys, 21),
+    ];
+    for (msg, tag) in cases {
+        let mut buf = encode(&msg);
+        assert_eq!(buf.as_slice()[0], tag);
+        assert_eq!(Message::decode(&mut buf).unwrap(), msg);
+        assert_eq!(buf.remaining(), 0);
+    }
+    assert_eq!(encode(&Message::NewKeys).as_slice(), &[21]);
+}
+
+#[test]
+fn test_enum_rejects_unknown_discriminant() {
+    let mut buf = Buffer::from_vec(vec![3]);
+    assert_eq!(
+        Message::decode(&mut buf),
+        Err(BufferError::InvalidData("unknown message discriminant"))
+    );
+}
+
+#[test]
+fn test_string_limit_is_enforced() {
+    // SSH_MSG_IGNORE with a 16 MiB string length
+    let mut buf = Buffer::from_vec(vec![2, 0x01, 0, 0, 0]);
+    assert_eq!(Message::decode(&mut buf), Err(BufferError::InvalidString));
+}
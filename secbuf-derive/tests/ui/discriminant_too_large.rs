@@ -0,0 +1,9 @@
+use secbuf::Encode;
+
+#[derive(Encode)]
+enum Message {
+    Ping = 255,
+    Pong = 256,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use secbuf::Encode;
+
+#[derive(Encode)]
+enum Message {
+    Ping = 1,
+    Pong = 0,
+    Close,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use secbuf::Encode;
+
+const PING: isize = 1;
+
+#[derive(Encode)]
+enum Message {
+    Ping = PING,
+    Pong,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use secbuf::Decode;
+
+#[derive(Decode)]
+union Word {
+    int: u32,
+    bytes: [u8; 4],
+}
+
+fn main() {}
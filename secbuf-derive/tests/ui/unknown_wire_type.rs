@@ -0,0 +1,9 @@
+use secbuf::Encode;
+
+#[derive(Encode)]
+struct Reply {
+    #[wire(bignum)]
+    f: Vec<u8>,
+}
+
+fn main() {}
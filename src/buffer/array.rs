@@ -0,0 +1,456 @@
+// src/buffer/array.rs
+//! [`ArrayBuffer`]: a fixed-capacity buffer stored inline
+
+use super::ops::MAX_STRING_LEN;
+use crate::error::{BufferError, Result};
+use crate::secret::SecretBytes;
+use alloc::vec::Vec;
+use zeroize::Zeroize;
+
+/// A buffer of `N` bytes stored inline, with the same put/get/string API as
+/// [`Buffer`](super::Buffer).
+///
+/// Nothing is allocated, so small handshake messages can be built and parsed
+/// on the stack (or in a `static`), including without `std`.  The storage is
+/// zeroized on drop.  Writes past `N` bytes fail with
+/// [`BufferError::BufferOverflow`]; there is no growth policy.
+///
+/// Use [`BufferRead`](super::BufferRead) and
+/// [`BufferWrite`](super::BufferWrite) to write protocol code that accepts
+/// either kind of buffer.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::ArrayBuffer;
+/// # use secbuf::BufferError;
+///
+/// let mut buf = ArrayBuffer::<64>::new();
+/// buf.put_byte(20)?; // SSH_MSG_KEXINIT
+/// buf.put_string(b"curve25519-sha256")?;
+///
+/// buf.set_pos(0)?;
+/// assert_eq!(buf.get_byte()?, 20);
+/// assert_eq!(buf.get_str_ref()?, "curve25519-sha256");
+/// # Ok::<(), BufferError>(())
+/// ```
+#[derive(Clone, Zeroize)]
+#[zeroize(drop)]
+pub struct ArrayBuffer<const N: usize> {
+    data: [u8; N],
+    /// Current read/write position
+    pos: usize,
+    /// Length of valid data
+    len: usize,
+}
+
+impl<const N: usize> ArrayBuffer<N> {
+    /// Creates an empty, zeroed buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            data: [0; N],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the storage size, `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length of valid data in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer contains no valid data.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the current read/write position.
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes available to read from current position.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.len - self.pos
+    }
+
+    /// Checks if at least `count` bytes are available to read.
+    #[inline]
+    pub fn has_remaining(&self, count: usize) -> bool {
+        self.remaining() >= count
+    }
+
+    /// Sets the read/write position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::PositionOutOfBounds`] if `pos` exceeds the buffer length.
+    pub fn set_pos(&mut self, pos: usize) -> Result<()> {
+        if pos > self.len {
+            return Err(BufferError::PositionOutOfBounds);
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// Sets the length of valid data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::SizeTooBig`] if `len` exceeds `N`.
+    pub fn set_len(&mut self, len: usize) -> Result<()> {
+        if len > N {
+            return Err(BufferError::SizeTooBig);
+        }
+        self.len = len;
+        self.pos = self.pos.min(len);
+        Ok(())
+    }
+
+    /// Increments the position by `incr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::IncrementTooLarge`] if the new position would
+    /// exceed the buffer length.
+    pub fn incr_pos(&mut self, incr: usize) -> Result<()> {
+        if incr > self.remaining() {
+            return Err(BufferError::IncrementTooLarge);
+        }
+        self.pos += incr;
+        Ok(())
+    }
+
+    /// Resets the buffer for reuse by clearing position and length.
+    ///
+    /// This does not zero the contents. Use [`burn`](Self::burn) for secure
+    /// erasure.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.pos = 0;
+        self.len = 0;
+    }
+
+    /// Securely zeros the storage and resets position and length.
+    pub fn burn(&mut self) {
+        self.data.zeroize();
+        self.pos = 0;
+        self.len = 0;
+    }
+
+    /// Returns a slice of all valid data in the buffer.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Returns a mutable slice of all valid data.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.data[..self.len]
+    }
+
+    /// Bytes that can be written at the current position.
+    #[inline]
+    pub(crate) fn writable_room(&self) -> usize {
+        N - self.pos
+    }
+
+    #[inline]
+    fn ensure_writable(&self, op: &'static str, n: usize) -> Result<()> {
+        if n > self.writable_room() {
+            return Err(BufferError::overflow(op, self.pos, n, self.writable_room()));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn ensure_readable(&self, op: &'static str, n: usize) -> Result<()> {
+        if n > self.remaining() {
+            return Err(BufferError::overflow(op, self.pos, n, self.remaining()));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn put_raw(&mut self, op: &'static str, bytes: &[u8]) -> Result<()> {
+        self.ensure_writable(op, bytes.len())?;
+        self.data[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+        if self.pos > self.len {
+            self.len = self.pos;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn take(&mut self, op: &'static str, n: usize) -> Result<&[u8]> {
+        self.ensure_readable(op, n)?;
+        let start = self.pos;
+        self.pos += n;
+        Ok(&self.data[start..self.pos])
+    }
+
+    #[inline]
+    fn take_array<const M: usize>(&mut self, op: &'static str) -> Result<[u8; M]> {
+        let mut out = [0; M];
+        out.copy_from_slice(self.take(op, M)?);
+        Ok(out)
+    }
+
+    /// Writes a single byte.
+    #[inline]
+    pub fn put_byte(&mut self, val: u8) -> Result<()> {
+        self.put_raw("put_byte", &[val])
+    }
+
+    /// Reads a single byte.
+    #[inline]
+    pub fn get_byte(&mut self) -> Result<u8> {
+        Ok(self.take("get_byte", 1)?[0])
+    }
+
+    /// Writes a `u8`. Alias of [`put_byte`](Self::put_byte).
+    #[inline]
+    pub fn put_u8(&mut self, val: u8) -> Result<()> {
+        self.put_byte(val)
+    }
+
+    /// Reads a `u8`. Alias of [`get_byte`](Self::get_byte).
+    #[inline]
+    pub fn get_u8(&mut self) -> Result<u8> {
+        self.get_byte()
+    }
+
+    /// Writes an SSH `boolean` (RFC 4251 §5): `1` for true, `0` for false.
+    #[inline]
+    pub fn put_bool(&mut self, val: bool) -> Result<()> {
+        self.put_byte(val as u8)
+    }
+
+    /// Reads a boolean (0 = false, non-zero = true).
+    #[inline]
+    pub fn get_bool(&mut self) -> Result<bool> {
+        Ok(self.get_byte()? != 0)
+    }
+
+    /// Writes a `u16` in big-endian format with bounds checking.
+    #[inline]
+    pub fn put_u16(&mut self, val: u16) -> Result<()> {
+        self.put_raw("put_u16", &val.to_be_bytes())
+    }
+
+    /// Reads a `u16` in big-endian format with bounds checking.
+    #[inline]
+    pub fn get_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.take_array("get_u16")?))
+    }
+
+    /// Writes a `u32` in big-endian format with bounds checking.
+    #[inline]
+    pub fn put_u32(&mut self, val: u32) -> Result<()> {
+        self.put_raw("put_u32", &val.to_be_bytes())
+    }
+
+    /// Reads a `u32` in big-endian format with bounds checking.
+    #[inline]
+    pub fn get_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take_array("get_u32")?))
+    }
+
+    /// Writes a `u64` in big-endian format with bounds checking.
+    #[inline]
+    pub fn put_u64(&mut self, val: u64) -> Result<()> {
+        self.put_raw("put_u64", &val.to_be_bytes())
+    }
+
+    /// Reads a `u64` in big-endian format with bounds checking.
+    #[inline]
+    pub fn get_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take_array("get_u64")?))
+    }
+
+    /// Writes bytes with a single bounds check.
+    #[inline]
+    pub fn put_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.put_raw("put_bytes", bytes)
+    }
+
+    /// Reads bytes, returning an owned `Vec`.
+    ///
+    /// The returned `Vec` is **not** zeroized on drop.  Use
+    /// [`get_bytes_secret`](Self::get_bytes_secret) for key material, or
+    /// [`get_bytes_ref`](Self::get_bytes_ref) to avoid the copy entirely.
+    #[inline]
+    pub fn get_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+        Ok(self.take("get_bytes", len)?.to_vec())
+    }
+
+    /// Reads bytes into a [`SecretBytes`] that is zeroized on drop.
+    #[inline]
+    pub fn get_bytes_secret(&mut self, len: usize) -> Result<SecretBytes> {
+        Ok(SecretBytes::from_slice(self.get_bytes_ref(len)?))
+    }
+
+    /// Reads bytes as a slice reference (zero-copy).
+    #[inline]
+    pub fn get_bytes_ref(&mut self, len: usize) -> Result<&[u8]> {
+        self.take("get_bytes_ref", len)
+    }
+
+    /// Writes an SSH-style string (4-byte length prefix + data).
+    #[inline]
+    pub fn put_string(&mut self, s: &[u8]) -> Result<()> {
+        if s.len() > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        self.put_u32(s.len() as u32)?;
+        self.put_bytes(s)
+    }
+
+    /// Reads the length of an SSH-style string and checks it against the
+    /// string limit.
+    #[inline]
+    fn get_string_len(&mut self) -> Result<usize> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        Ok(len)
+    }
+
+    /// Reads an SSH-style string (4-byte length prefix + data).
+    ///
+    /// The returned `Vec` is **not** zeroized on drop.  Use
+    /// [`get_string_secret`](Self::get_string_secret) for passwords and keys,
+    /// or [`get_string_ref`](Self::get_string_ref) to avoid the copy entirely.
+    #[inline]
+    pub fn get_string(&mut self) -> Result<Vec<u8>> {
+        let len = self.get_string_len()?;
+        self.get_bytes(len)
+    }
+
+    /// Reads an SSH-style string into a [`SecretBytes`] that is zeroized on drop.
+    #[inline]
+    pub fn get_string_secret(&mut self) -> Result<SecretBytes> {
+        Ok(SecretBytes::from_slice(self.get_string_ref()?))
+    }
+
+    /// Reads an SSH-style string as a slice reference (zero-copy).
+    #[inline]
+    pub fn get_string_ref(&mut self) -> Result<&[u8]> {
+        let len = self.get_string_len()?;
+        self.get_bytes_ref(len)
+    }
+
+    /// Reads an SSH-style string as a UTF-8 `&str` (zero-copy).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if the payload is not valid UTF-8.
+    #[inline]
+    pub fn get_str_ref(&mut self) -> Result<&str> {
+        let bytes = self.get_string_ref()?;
+        core::str::from_utf8(bytes).map_err(|_| BufferError::InvalidString)
+    }
+
+    /// Skips over an SSH-style string without reading the data.
+    #[inline]
+    pub fn eat_string(&mut self) -> Result<()> {
+        let len = self.get_string_len()?;
+        self.incr_pos(len)
+    }
+}
+
+impl<const N: usize> Default for ArrayBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayBuffer<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+
+    #[test]
+    fn test_round_trip_matches_buffer() {
+        let mut arr = ArrayBuffer::<64>::new();
+        let mut buf = Buffer::new(64);
+        arr.put_u32(0xDEAD_BEEF).unwrap();
+        arr.put_bool(true).unwrap();
+        arr.put_string(b"ssh-ed25519").unwrap();
+        arr.put_u64(7).unwrap();
+        buf.put_u32(0xDEAD_BEEF).unwrap();
+        buf.put_bool(true).unwrap();
+        buf.put_string(b"ssh-ed25519").unwrap();
+        buf.put_u64(7).unwrap();
+        assert_eq!(arr.as_slice(), buf.as_slice());
+
+        arr.set_pos(0).unwrap();
+        assert_eq!(arr.get_u32().unwrap(), 0xDEAD_BEEF);
+        assert!(arr.get_bool().unwrap());
+        assert_eq!(arr.get_string_ref().unwrap(), b"ssh-ed25519");
+        assert_eq!(arr.get_u64().unwrap(), 7);
+        assert_eq!(arr.remaining(), 0);
+    }
+
+    #[test]
+    fn test_overflow_reports_context() {
+        let mut arr = ArrayBuffer::<6>::new();
+        arr.put_u32(1).unwrap();
+        assert_eq!(
+            arr.put_u32(2),
+            Err(BufferError::overflow("put_u32", 4, 4, 2))
+        );
+        assert_eq!(arr.len(), 4);
+
+        arr.set_pos(2).unwrap();
+        assert_eq!(
+            arr.get_u32(),
+            Err(BufferError::overflow("get_u32", 2, 4, 2))
+        );
+        assert_eq!(arr.pos(), 2);
+    }
+
+    #[test]
+    fn test_string_limits() {
+        let mut arr = ArrayBuffer::<16>::new();
+        arr.put_u32(MAX_STRING_LEN as u32 + 1).unwrap();
+        arr.set_pos(0).unwrap();
+        assert_eq!(arr.get_string_ref(), Err(BufferError::InvalidString));
+
+        let mut arr = ArrayBuffer::<16>::new();
+        arr.put_string(&[0xFF, 0xFE]).unwrap();
+        arr.set_pos(0).unwrap();
+        assert_eq!(arr.get_str_ref(), Err(BufferError::InvalidString));
+    }
+
+    #[test]
+    fn test_burn_and_len() {
+        let mut arr = ArrayBuffer::<8>::default();
+        arr.put_bytes(b"secret").unwrap();
+        arr.burn();
+        assert!(arr.is_empty());
+        arr.set_len(8).unwrap();
+        assert_eq!(arr.as_slice(), &[0; 8]);
+        assert_eq!(arr.set_len(9), Err(BufferError::SizeTooBig));
+    }
+}
@@ -0,0 +1,150 @@
+// src/buffer/bytes_compat.rs
+//! [`bytes`] integration for [`Buffer`] (enabled by the `bytes` feature).
+//!
+//! - [`Buf`] reads `pos..len` and advances `pos`.
+//! - [`BufMut`] writes at `pos`, extends `len`, and grows the storage when the
+//!   [`GrowthPolicy`](super::GrowthPolicy) allows it.  Like
+//!   [`Buffer::reserve`], it panics if locked or guarded storage cannot be
+//!   grown.
+//! - Conversions to and from [`BytesMut`] copy the data and then wipe the
+//!   source, so the secret only ever lives in one place.
+
+use super::core::Buffer;
+use ::bytes::buf::UninitSlice;
+use ::bytes::{Buf, BufMut, BytesMut};
+use zeroize::Zeroize;
+
+/// Chunk size requested from the growth policy when `chunk_mut` finds the
+/// current storage full.
+const GROW_CHUNK: usize = 64;
+
+impl Buf for Buffer {
+    #[inline]
+    fn remaining(&self) -> usize {
+        Buffer::remaining(self)
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        &self.data[self.pos..self.len]
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= Buffer::remaining(self),
+            "advance past end of Buffer: {} > {}",
+            cnt,
+            Buffer::remaining(self)
+        );
+        self.pos += cnt;
+    }
+}
+
+unsafe impl BufMut for Buffer {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        self.writable_room()
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            self.pos + cnt <= self.data.len(),
+            "advance_mut past end of Buffer storage"
+        );
+        self.pos += cnt;
+        if self.pos > self.len {
+            self.len = self.pos;
+        }
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        if self.pos == self.data.len() {
+            // Grow by at least one chunk if the policy allows.  `remaining_mut`
+            // already promised this room, and `put_slice` loops until it gets
+            // it, so an allocation failure cannot become an empty chunk.
+            let want = GROW_CHUNK.min(self.writable_room());
+            if let Err(err) = self.ensure_writable("chunk_mut", want) {
+                panic!("cannot grow Buffer storage: {}", err);
+            }
+        }
+        UninitSlice::new(&mut self.data[self.pos..])
+    }
+}
+
+impl From<Buffer> for BytesMut {
+    /// Copies the valid data into a `BytesMut` and burns the source buffer.
+    fn from(mut buf: Buffer) -> Self {
+        let bytes = BytesMut::from(buf.as_slice());
+        buf.burn();
+        bytes
+    }
+}
+
+impl From<BytesMut> for Buffer {
+    /// Copies the data into a new `Buffer` and zeroizes the source
+    /// `BytesMut`, including its spare capacity.
+    fn from(mut bytes: BytesMut) -> Self {
+        let buf = Buffer::from_vec(bytes.to_vec());
+        bytes.as_mut().zeroize();
+        bytes.spare_capacity_mut().zeroize();
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buf_reads_valid_region() {
+        let mut buf = Buffer::new(32);
+        buf.put_u32(7).unwrap();
+        buf.put_bytes(b"tail").unwrap();
+        buf.set_pos(0).unwrap();
+
+        assert_eq!(Buf::remaining(&buf), 8);
+        assert_eq!(Buf::get_u32(&mut buf), 7);
+        assert_eq!(buf.chunk(), b"tail");
+        buf.advance(4);
+        assert!(!Buf::has_remaining(&buf));
+    }
+
+    #[test]
+    #[should_panic(expected = "advance past end")]
+    fn test_buf_advance_past_end_panics() {
+        let mut buf = Buffer::from_vec(vec![1, 2]);
+        buf.advance(3);
+    }
+
+    #[test]
+    fn test_buf_mut_writes_and_grows() {
+        let mut fixed = Buffer::new(4);
+        BufMut::put_u16(&mut fixed, 0xABCD);
+        assert_eq!(fixed.remaining_mut(), 2);
+        assert_eq!(fixed.as_slice(), &[0xAB, 0xCD]);
+
+        let mut growable = Buffer::growable(0, 1000);
+        growable.put_slice(&[9; 300]);
+        assert_eq!(growable.len(), 300);
+        assert_eq!(growable.remaining_mut(), 700);
+    }
+
+    #[test]
+    fn test_bytes_mut_conversions() {
+        let mut src = BytesMut::with_capacity(64);
+        src.extend_from_slice(b"secret material");
+        let ptr = src.as_ptr();
+
+        let buf = Buffer::from(src.split());
+        assert_eq!(buf.as_slice(), b"secret material");
+        // The split-off half was consumed; the original allocation is wiped.
+        let wiped = unsafe { core::slice::from_raw_parts(ptr, 15) };
+        assert_eq!(wiped, &[0u8; 15]);
+
+        let back = BytesMut::from(buf);
+        assert_eq!(&back[..], b"secret material");
+    }
+}
@@ -0,0 +1,250 @@
+// src/buffer/checkpoint.rs
+//! Transactional parsing and writing with [`Checkpoint`] guards
+
+use super::core::Buffer;
+use zeroize::Zeroize;
+
+/// A saved position and length that a [`Buffer`] rolls back to unless
+/// [`commit`](Self::commit) is called.
+///
+/// Returned by [`Buffer::checkpoint`].  The guard dereferences to the
+/// buffer, so parsing and writing continue through it.  When it drops
+/// without being committed:
+///
+/// - `pos` is restored, so a failed parse leaves the buffer where it was.
+/// - `len` is truncated back and the storage past the saved length is
+///   zeroized, so a half-written message leaves nothing behind — even if
+///   `len` was shrunk again with [`set_len`](Buffer::set_len) before the
+///   rollback.  Only the bytes up to the highest length seen through the
+///   guard are wiped, so a read-only rollback costs nothing extra.  A
+///   length that was raised and lowered again through a single
+///   `&mut Buffer` borrowed from the guard is not seen.
+///
+/// Bytes overwritten *inside* the saved length are not restored.
+///
+/// Checkpoints nest: calling `checkpoint()` on a guard creates an inner
+/// transaction, and committing it only folds its changes into the outer one.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::Buffer;
+/// # use secbuf::BufferError;
+///
+/// let mut buf = Buffer::new(64);
+/// buf.put_u32(7)?;
+/// buf.put_byte(1)?; // truncated packet: a u32 then only one byte
+/// buf.set_pos(0)?;
+///
+/// fn parse(buf: &mut Buffer) -> Result<(u32, u16), BufferError> {
+///     let mut cp = buf.checkpoint();
+///     let parsed = (cp.get_u32()?, cp.get_u16()?);
+///     cp.commit();
+///     Ok(parsed)
+/// }
+///
+/// assert!(parse(&mut buf).is_err());
+/// assert_eq!(buf.pos(), 0); // rolled back; retry once more data arrives
+/// # Ok::<(), BufferError>(())
+/// ```
+#[must_use = "dropping a Checkpoint immediately rolls it back"]
+pub struct Checkpoint<'a> {
+    buf: &'a mut Buffer,
+    pos: usize,
+    len: usize,
+    /// Highest `len` observed through the guard.
+    high: usize,
+    committed: bool,
+}
+
+impl Checkpoint<'_> {
+    /// Keeps all changes made since the checkpoint.
+    #[inline]
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    /// Discards all changes made since the checkpoint.  Equivalent to
+    /// dropping the guard.
+    #[inline]
+    pub fn rollback(self) {}
+
+    /// Position saved when the checkpoint was taken.
+    #[inline]
+    pub fn saved_pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Length saved when the checkpoint was taken.
+    #[inline]
+    pub fn saved_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl core::ops::Deref for Checkpoint<'_> {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        self.buf
+    }
+}
+
+impl core::ops::DerefMut for Checkpoint<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Catches a write that is followed by a shrinking `set_len`.
+        self.high = self.high.max(self.buf.len);
+        self.buf
+    }
+}
+
+impl Drop for Checkpoint<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        let buf = &mut *self.buf;
+        // Bytes written past the saved length may sit beyond the current
+        // `len` too, where `set_len` or `resize` would expose them again.
+        let high = self.high.max(buf.len).min(buf.data.len());
+        if self.len < high {
+            buf.data[self.len..high].zeroize();
+        }
+        buf.len = buf.len.min(self.len);
+        // The buffer may have been truncated below the saved length.
+        buf.pos = self.pos.min(buf.len);
+    }
+}
+
+impl Buffer {
+    /// Saves the current position and length, returning a guard that
+    /// restores them on drop unless committed.
+    ///
+    /// See [`Checkpoint`] for details.
+    #[inline]
+    pub fn checkpoint(&mut self) -> Checkpoint<'_> {
+        Checkpoint {
+            pos: self.pos,
+            len: self.len,
+            high: self.len,
+            buf: self,
+            committed: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_keeps_changes() {
+        let mut buf = Buffer::new(32);
+        let mut cp = buf.checkpoint();
+        cp.put_u32(42).unwrap();
+        cp.commit();
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.pos(), 4);
+    }
+
+    #[test]
+    fn test_rollback_restores_read_position() {
+        let mut buf = Buffer::from_vec(vec![0, 0, 0, 1, 0xFF]);
+        {
+            let mut cp = buf.checkpoint();
+            assert_eq!(cp.get_u32().unwrap(), 1);
+            assert!(cp.get_u16().is_err());
+        }
+        assert_eq!(buf.pos(), 0);
+        assert_eq!(buf.len(), 5);
+    }
+
+    #[test]
+    fn test_rollback_truncates_and_zeroizes_writes() {
+        let mut buf = Buffer::new(32);
+        buf.put_bytes(b"keep").unwrap();
+        {
+            let mut cp = buf.checkpoint();
+            cp.put_bytes(b"half-written secret").unwrap();
+        }
+        assert_eq!(buf.as_slice(), b"keep");
+        assert_eq!(buf.pos(), 4);
+        buf.set_len(23).unwrap();
+        assert_eq!(&buf.as_slice()[4..], &[0u8; 19]);
+    }
+
+    #[test]
+    fn test_rollback_zeroizes_writes_hidden_by_set_len() {
+        let mut buf = Buffer::new(32);
+        buf.put_bytes(b"keep").unwrap();
+        {
+            let mut cp = buf.checkpoint();
+            cp.put_bytes(b"half-written secret").unwrap();
+            cp.set_len(4).unwrap();
+        }
+        assert_eq!(buf.as_slice(), b"keep");
+        buf.resize(32).unwrap();
+        buf.set_len(32).unwrap();
+        assert_eq!(&buf.as_slice()[4..], &[0u8; 28]);
+    }
+
+    #[test]
+    fn test_read_only_rollback_leaves_tail_alone() {
+        let mut buf = Buffer::new(32);
+        buf.put_bytes(b"header|stale tail").unwrap();
+        buf.set_len(6).unwrap();
+        buf.set_pos(0).unwrap();
+        {
+            let mut cp = buf.checkpoint();
+            assert_eq!(cp.get_bytes(6).unwrap(), b"header");
+        }
+        assert_eq!(buf.pos(), 0);
+        buf.set_len(17).unwrap();
+        assert_eq!(buf.as_slice(), b"header|stale tail");
+    }
+
+    #[test]
+    fn test_rollback_after_growth() {
+        let mut buf = Buffer::growable(4, 1024);
+        buf.put_u16(0xABCD).unwrap();
+        {
+            let mut cp = buf.checkpoint();
+            cp.put_bytes(&[0x11; 200]).unwrap();
+        }
+        assert_eq!(buf.as_slice(), &[0xAB, 0xCD]);
+        buf.set_len(202).unwrap();
+        assert!(buf.as_slice()[2..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_nested_checkpoints() {
+        let mut buf = Buffer::new(32);
+        let mut outer = buf.checkpoint();
+        outer.put_byte(1).unwrap();
+        {
+            let mut inner = outer.checkpoint();
+            inner.put_byte(2).unwrap();
+            inner.commit();
+        }
+        {
+            let mut inner = outer.checkpoint();
+            inner.put_byte(3).unwrap();
+            // rolled back
+        }
+        assert_eq!(outer.as_slice(), &[1, 2]);
+        outer.rollback();
+        assert!(buf.is_empty());
+        assert_eq!(buf.pos(), 0);
+    }
+
+    #[test]
+    fn test_rollback_after_truncation() {
+        let mut buf = Buffer::from_vec(b"abcdef".to_vec());
+        buf.set_pos(5).unwrap();
+        {
+            let mut cp = buf.checkpoint();
+            cp.set_len(2).unwrap();
+        }
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.pos(), 2);
+    }
+}
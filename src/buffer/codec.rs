@@ -0,0 +1,263 @@
+// src/buffer/codec.rs
+//! [`Encode`] and [`Decode`]: whole-value conversion to and from the SSH
+//! wire format
+
+use super::core::Buffer;
+use crate::error::{BufferError, Result};
+use crate::secret::SecretBytes;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A value with an SSH wire encoding.
+///
+/// Implemented for the SSH primitive types: `u8` (`byte`), `bool`
+/// (`boolean`), `u16`, `u32` (`uint32`), `u64` (`uint64`), `Vec<u8>`, `String` and
+/// [`SecretBytes`] (`string`), and `[u8; N]` (`byte[n]`, written without a
+/// length).  With the `derive` feature, `#[derive(Encode)]` implements it for
+/// structs and message enums.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::{Buffer, Encode};
+/// # use secbuf::BufferError;
+///
+/// let mut buf = Buffer::new(32);
+/// 5u8.encode(&mut buf)?;
+/// String::from("ssh-userauth").encode(&mut buf)?;
+/// assert_eq!(buf.as_slice(), b"\x05\0\0\0\x0cssh-userauth");
+/// # Ok::<(), BufferError>(())
+/// ```
+pub trait Encode {
+    /// Writes `self` at the buffer's current position.
+    fn encode(&self, buf: &mut Buffer) -> Result<()>;
+}
+
+/// A value that can be parsed from its SSH wire encoding.
+///
+/// See [`Encode`] for the provided implementations.
+pub trait Decode: Sized {
+    /// Reads a value from the buffer's current position.
+    fn decode(buf: &mut Buffer) -> Result<Self>;
+}
+
+macro_rules! impl_codec {
+    ($($ty:ty => $put:ident, $get:ident;)*) => {
+        $(
+            impl Encode for $ty {
+                #[inline]
+                fn encode(&self, buf: &mut Buffer) -> Result<()> {
+                    buf.$put(*self)
+                }
+            }
+
+            impl Decode for $ty {
+                #[inline]
+                fn decode(buf: &mut Buffer) -> Result<Self> {
+                    buf.$get()
+                }
+            }
+        )*
+    };
+}
+
+impl_codec! {
+    u8 => put_u8, get_u8;
+    bool => put_bool, get_bool;
+    u16 => put_u16, get_u16;
+    u32 => put_u32, get_u32;
+    u64 => put_u64, get_u64;
+}
+
+impl Encode for Vec<u8> {
+    #[inline]
+    fn encode(&self, buf: &mut Buffer) -> Result<()> {
+        buf.put_string(self)
+    }
+}
+
+impl Decode for Vec<u8> {
+    #[inline]
+    fn decode(buf: &mut Buffer) -> Result<Self> {
+        buf.get_string()
+    }
+}
+
+impl Encode for String {
+    #[inline]
+    fn encode(&self, buf: &mut Buffer) -> Result<()> {
+        buf.put_string(self.as_bytes())
+    }
+}
+
+impl Decode for String {
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if the string is not UTF-8.
+    #[inline]
+    fn decode(buf: &mut Buffer) -> Result<Self> {
+        buf.get_str_ref().map(ToOwned::to_owned)
+    }
+}
+
+impl Encode for SecretBytes {
+    #[inline]
+    fn encode(&self, buf: &mut Buffer) -> Result<()> {
+        buf.put_string(self.expose_secret())
+    }
+}
+
+impl Decode for SecretBytes {
+    #[inline]
+    fn decode(buf: &mut Buffer) -> Result<Self> {
+        buf.get_string_secret()
+    }
+}
+
+impl<const N: usize> Encode for [u8; N] {
+    #[inline]
+    fn encode(&self, buf: &mut Buffer) -> Result<()> {
+        buf.put_bytes(self)
+    }
+}
+
+impl<const N: usize> Decode for [u8; N] {
+    #[inline]
+    fn decode(buf: &mut Buffer) -> Result<Self> {
+        let mut out = [0; N];
+        out.copy_from_slice(buf.get_bytes_ref(N)?);
+        Ok(out)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    #[inline]
+    fn encode(&self, buf: &mut Buffer) -> Result<()> {
+        (**self).encode(buf)
+    }
+}
+
+/// Support code for `secbuf-derive`; not a public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+    use crate::buffer::PrefixWidth;
+
+    /// Field types accepted by `#[wire(mpint)]`.
+    pub trait MpintField: Sized {
+        /// Big-endian magnitude to encode.
+        fn magnitude(&self) -> &[u8];
+        /// Builds the field from a decoded magnitude.
+        fn from_magnitude(magnitude: SecretBytes) -> Self;
+    }
+
+    impl MpintField for SecretBytes {
+        #[inline]
+        fn magnitude(&self) -> &[u8] {
+            self.expose_secret()
+        }
+
+        #[inline]
+        fn from_magnitude(magnitude: SecretBytes) -> Self {
+            magnitude
+        }
+    }
+
+    impl MpintField for Vec<u8> {
+        #[inline]
+        fn magnitude(&self) -> &[u8] {
+            self
+        }
+
+        #[inline]
+        fn from_magnitude(magnitude: SecretBytes) -> Self {
+            magnitude.expose_secret().to_vec()
+        }
+    }
+
+    /// Encodes an `mpint` field (`#[wire(mpint)]`).
+    #[inline]
+    pub fn encode_mpint<T: MpintField>(value: &T, buf: &mut Buffer) -> Result<()> {
+        buf.put_mpint(value.magnitude())
+    }
+
+    /// Decodes an `mpint` field (`#[wire(mpint)]`).
+    #[inline]
+    pub fn decode_mpint<T: MpintField>(buf: &mut Buffer) -> Result<T> {
+        buf.get_mpint().map(T::from_magnitude)
+    }
+
+    /// Encodes `value` inside an SSH string (`#[wire(string)]`).
+    pub fn encode_nested<T: Encode + ?Sized>(value: &T, buf: &mut Buffer) -> Result<()> {
+        let mut inner = buf.begin_length_prefixed(PrefixWidth::U32)?;
+        value.encode(&mut inner)?;
+        inner.finish()
+    }
+
+    /// Decodes a value that must fill an SSH string exactly
+    /// (`#[wire(string)]`).
+    pub fn decode_nested<T: Decode>(buf: &mut Buffer) -> Result<T> {
+        let mut inner = buf.get_string_reader()?;
+        let value = T::decode(&mut inner)?;
+        if inner.remaining() != 0 {
+            return Err(BufferError::InvalidData("trailing data in string"));
+        }
+        Ok(value)
+    }
+
+    /// The error returned for an unknown message enum discriminant.
+    #[cold]
+    pub fn unknown_discriminant() -> BufferError {
+        BufferError::InvalidData("unknown message discriminant")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::__private::*;
+    use super::*;
+
+    fn round_trip<T: Encode + Decode + PartialEq + core::fmt::Debug>(value: T) {
+        let mut buf = Buffer::new(64);
+        value.encode(&mut buf).unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(T::decode(&mut buf).unwrap(), value);
+        assert_eq!(buf.remaining(), 0);
+    }
+
+    #[test]
+    fn test_primitives_round_trip() {
+        round_trip(0xABu8);
+        round_trip(true);
+        round_trip(0xBEEFu16);
+        round_trip(0xDEAD_BEEFu32);
+        round_trip(u64::MAX);
+        round_trip(b"blob".to_vec());
+        round_trip(String::from("ssh-ed25519"));
+        round_trip([7u8; 16]);
+    }
+
+    #[test]
+    fn test_string_decode_rejects_invalid_utf8() {
+        let mut buf = Buffer::new(16);
+        buf.put_string(&[0xFF, 0xFE]).unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(String::decode(&mut buf), Err(BufferError::InvalidString));
+    }
+
+    #[test]
+    fn test_nested_round_trip() {
+        let mut buf = Buffer::new(32);
+        encode_nested(&0x0102_0304u32, &mut buf).unwrap();
+        assert_eq!(buf.as_slice(), &[0, 0, 0, 4, 1, 2, 3, 4]);
+
+        buf.set_pos(0).unwrap();
+        assert_eq!(decode_nested::<u32>(&mut buf).unwrap(), 0x0102_0304);
+        buf.set_pos(0).unwrap();
+        assert_eq!(
+            decode_nested::<u16>(&mut buf),
+            Err(BufferError::InvalidData("trailing data in string"))
+        );
+    }
+}
@@ -4,7 +4,11 @@
 //! This module provides the fundamental [`Buffer`] type with position tracking
 //! and automatic secure memory zeroing on drop.
 
+use super::storage::Storage;
 use crate::error::{BufferError, Result};
+use crate::secret::SecretBytes;
+use alloc::vec;
+use alloc::vec::Vec;
 use zeroize::Zeroize;
 
 /// Maximum single increment to prevent integer overflow
@@ -12,6 +16,50 @@ pub const BUF_MAX_INCR: usize = 1_000_000_000;
 /// Maximum buffer size (1GB)
 pub const BUF_MAX_SIZE: usize = 1_000_000_000;
 
+/// How a [`Buffer`] reacts to a write past the end of its storage.
+///
+/// The default is [`Fixed`](Self::Fixed), matching the historical behaviour:
+/// every `put_*` fails with [`BufferError::BufferOverflow`] once the buffer is
+/// full.  With [`Doubling`](Self::Doubling), the storage grows geometrically
+/// (at least doubling) until it reaches `max`.
+///
+/// Growth never leaves a stale copy behind: when the storage has to move to a
+/// larger allocation the old block is zeroized before it is freed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrowthPolicy {
+    /// Never grow; writes past the end fail.
+    #[default]
+    Fixed,
+    /// Grow by doubling, up to a hard ceiling of `max` bytes.
+    Doubling {
+        /// Maximum size in bytes. Must not exceed [`BUF_MAX_SIZE`].
+        max: usize,
+    },
+}
+
+/// Where a [`Buffer`]'s bytes are allocated.
+///
+/// Selectable per buffer with [`Buffer::with_alloc_mode`] and per pool with
+/// [`PoolConfig::alloc_mode`](crate::PoolConfig::alloc_mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AllocMode {
+    /// Ordinary heap allocation.
+    #[default]
+    Heap,
+    /// A dedicated mapping between two `PROT_NONE` guard pages, with the
+    /// data right-aligned against the trailing guard and a random canary in
+    /// front of it (Unix only).
+    ///
+    /// Writing past the end faults immediately; writing before the start
+    /// corrupts the canary, which is verified on drop and aborts the process
+    /// if it has changed.  Each buffer costs at least three pages and a
+    /// syscall per allocation, so reserve this for secrets.
+    Guarded,
+}
+
+/// Smallest storage size a growable buffer expands to.
+const MIN_GROW_SIZE: usize = 64;
+
 /// A high-performance linear buffer with position tracking.
 ///
 /// The buffer automatically and securely zeros its memory on drop using
@@ -37,11 +85,14 @@ pub const BUF_MAX_SIZE: usize = 1_000_000_000;
 #[zeroize(drop)]
 pub struct Buffer {
     /// Internal data storage (securely erased on drop)
-    pub(crate) data: Vec<u8>,
+    pub(crate) data: Storage,
     /// Current read/write position
     pub(crate) pos: usize,
     /// Length of valid data
     pub(crate) len: usize,
+    /// What to do when a write runs past the end of `data`
+    #[zeroize(skip)]
+    pub(crate) growth: GrowthPolicy,
 }
 
 impl Buffer {
@@ -73,9 +124,10 @@ impl Buffer {
             BUF_MAX_SIZE
         );
         Self {
-            data: vec![0; size],
+            data: Storage::Heap(vec![0; size]),
             pos: 0,
             len: 0,
+            growth: GrowthPolicy::Fixed,
         }
     }
 
@@ -109,9 +161,10 @@ impl Buffer {
             BUF_MAX_SIZE
         );
         Self {
-            data: Vec::with_capacity(capacity),
+            data: Storage::Heap(Vec::with_capacity(capacity)),
             pos: 0,
             len: 0,
+            growth: GrowthPolicy::Fixed,
         }
     }
 
@@ -131,12 +184,259 @@ impl Buffer {
     /// assert_eq!(buf.pos(), 0);
     /// ```
     pub fn from_vec(data: Vec<u8>) -> Self {
+        Self::from_storage(Storage::Heap(data))
+    }
+
+    /// Wraps existing storage; the whole writable area counts as valid data.
+    pub(crate) fn from_storage(data: Storage) -> Self {
         let len = data.len();
-        Self { data, pos: 0, len }
+        Self {
+            data,
+            pos: 0,
+            len,
+            growth: GrowthPolicy::Fixed,
+        }
+    }
+
+    /// Creates a buffer of `size` zeroed bytes using the given allocation
+    /// mode.
+    ///
+    /// `AllocMode::Heap` is equivalent to [`new`](Self::new).  Growth,
+    /// resizing and cloning keep the buffer in the same kind of allocation.
+    ///
+    /// # Errors
+    ///
+    /// - [`BufferError::SizeTooBig`] if `size` exceeds [`BUF_MAX_SIZE`].
+    /// - [`BufferError::Io`] if a guarded mapping cannot be created.
+    /// - [`BufferError::InvalidState`] if guarded allocation is requested on
+    ///   a non-Unix platform or without the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::{AllocMode, Buffer};
+    /// # use secbuf::BufferError;
+    ///
+    /// # #[cfg(all(unix, feature = "std"))] {
+    /// let mut buf = Buffer::with_alloc_mode(64, AllocMode::Guarded)?;
+    /// buf.put_bytes(b"api token")?;
+    /// assert!(buf.is_guarded());
+    /// # }
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn with_alloc_mode(size: usize, mode: AllocMode) -> Result<Self> {
+        if size > BUF_MAX_SIZE {
+            return Err(BufferError::SizeTooBig);
+        }
+        match mode {
+            AllocMode::Heap => Ok(Self::new(size)),
+            #[cfg(all(unix, feature = "std"))]
+            AllocMode::Guarded => {
+                let region = crate::locked::guarded::GuardedRegion::zeroed(size)?;
+                let mut buf = Self::from_storage(Storage::Guarded(region));
+                buf.len = 0;
+                Ok(buf)
+            }
+            #[cfg(not(all(unix, feature = "std")))]
+            AllocMode::Guarded => Err(BufferError::InvalidState(
+                "guarded allocation requires Unix and the `std` feature",
+            )),
+        }
+    }
+
+    /// Creates a buffer of `size` zeroed bytes in a guarded allocation.
+    ///
+    /// Shorthand for [`with_alloc_mode`](Self::with_alloc_mode) with
+    /// [`AllocMode::Guarded`].
+    #[inline]
+    pub fn guarded(size: usize) -> Result<Self> {
+        Self::with_alloc_mode(size, AllocMode::Guarded)
+    }
+
+    /// Creates a buffer that starts at `initial` bytes and grows on demand
+    /// up to `max` bytes.
+    ///
+    /// Equivalent to [`new`](Self::new) followed by
+    /// [`set_growth_policy`](Self::set_growth_policy) with
+    /// [`GrowthPolicy::Doubling`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` exceeds [`BUF_MAX_SIZE`] or `initial` exceeds `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::growable(4, 1024);
+    /// buf.put_bytes(&[0xAB; 100])?;
+    /// assert_eq!(buf.len(), 100);
+    /// assert!(buf.put_bytes(&[0; 1000]).is_err());
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn growable(initial: usize, max: usize) -> Self {
+        assert!(
+            max <= BUF_MAX_SIZE,
+            "Buffer growth limit {} exceeds maximum {}",
+            max,
+            BUF_MAX_SIZE
+        );
+        assert!(
+            initial <= max,
+            "Buffer initial size {} exceeds growth limit {}",
+            initial,
+            max
+        );
+        let mut buf = Self::new(initial);
+        buf.growth = GrowthPolicy::Doubling { max };
+        buf
+    }
+
+    /// Returns the buffer's growth policy.
+    #[inline]
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.growth
+    }
+
+    /// Sets the buffer's growth policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::SizeTooBig`] if a [`GrowthPolicy::Doubling`]
+    /// limit exceeds [`BUF_MAX_SIZE`].
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) -> Result<()> {
+        if let GrowthPolicy::Doubling { max } = policy {
+            if max > BUF_MAX_SIZE {
+                return Err(BufferError::SizeTooBig);
+            }
+        }
+        self.growth = policy;
+        Ok(())
+    }
+
+    /// Ensures `n` bytes can be written at `pos`, growing the storage if the
+    /// growth policy allows it.
+    ///
+    /// This is the single bounds check shared by every `put_*` method.
+    #[inline(always)]
+    pub(crate) fn ensure_writable(&mut self, op: &'static str, n: usize) -> Result<()> {
+        if n > self.data.len() - self.pos {
+            return self.grow_for_write(op, n);
+        }
+        Ok(())
+    }
+
+    /// Fails with [`BufferError::BufferOverflow`] unless `n` bytes remain to
+    /// be read at `pos`.
+    #[inline(always)]
+    pub(crate) fn ensure_readable(&self, op: &'static str, n: usize) -> Result<()> {
+        if n > self.remaining() {
+            return Err(BufferError::overflow(op, self.pos, n, self.remaining()));
+        }
+        Ok(())
+    }
+
+    /// Bytes that can still be written at `pos`, counting any growth the
+    /// policy allows.
+    #[inline]
+    pub(crate) fn writable_room(&self) -> usize {
+        let limit = match self.growth {
+            GrowthPolicy::Fixed => self.data.len(),
+            GrowthPolicy::Doubling { max } => max.max(self.data.len()),
+        };
+        limit.saturating_sub(self.pos)
+    }
+
+    #[cold]
+    fn grow_for_write(&mut self, op: &'static str, n: usize) -> Result<()> {
+        let overflow = || BufferError::overflow(op, self.pos, n, self.writable_room());
+        let GrowthPolicy::Doubling { max } = self.growth else {
+            return Err(overflow());
+        };
+        let needed = match self.pos.checked_add(n) {
+            Some(needed) if needed <= max => needed,
+            _ => return Err(overflow()),
+        };
+
+        let current = self.data.len();
+        let target = needed
+            .max(current.saturating_mul(2))
+            .max(MIN_GROW_SIZE)
+            .min(max);
+        if target > self.data.capacity() {
+            self.realloc_zeroizing(target)?;
+        }
+        // Use whatever capacity is already allocated (e.g. from
+        // `with_capacity`) before asking for more.
+        let new_len = target.max(self.data.capacity().min(max));
+        self.data.resize(new_len);
+        Ok(())
+    }
+
+    /// Extends `data` to `new_len` zeroed bytes, moving to a larger
+    /// allocation (and wiping the old one) only if capacity is exhausted.
+    ///
+    /// Only locked storage can fail to allocate.
+    pub(crate) fn grow_storage(&mut self, new_len: usize) -> Result<()> {
+        if new_len > self.data.capacity() {
+            self.realloc_zeroizing(new_len)?;
+        }
+        self.data.resize(new_len);
+        Ok(())
+    }
+
+    /// Shortens `data` to `new_len`, wiping the discarded bytes so they do
+    /// not linger in the spare capacity.
+    pub(crate) fn truncate_zeroizing(&mut self, new_len: usize) {
+        if new_len < self.data.len() {
+            self.data[new_len..].zeroize();
+            self.data.truncate(new_len);
+        }
+    }
+
+    /// Moves the contents into a fresh allocation of `new_cap` bytes and
+    /// zeroizes the old block before it is freed.
+    ///
+    /// `Vec`'s own growth paths hand the old block straight back to the
+    /// allocator with its contents intact; this never does.  The new
+    /// allocation is of the same kind (heap or locked) as the old one.
+    pub(crate) fn realloc_zeroizing(&mut self, new_cap: usize) -> Result<()> {
+        let fresh = self.data.with_capacity_like(new_cap)?;
+        self.move_to_storage(fresh);
+        Ok(())
+    }
+
+    /// Copies as much of `data` as fits into the empty storage `fresh`,
+    /// switches to it, and zeroizes the old storage before it is freed.
+    pub(crate) fn move_to_storage(&mut self, mut fresh: Storage) {
+        let keep = self.data.len().min(fresh.capacity());
+        fresh.resize(keep);
+        fresh.copy_from_slice(&self.data[..keep]);
+        let mut old = core::mem::replace(&mut self.data, fresh);
+        // Storage::zeroize wipes the spare capacity as well as the elements.
+        old.zeroize();
+    }
+
+    /// Returns `true` if the buffer lives in `mlock`ed memory (see
+    /// [`LockedBuffer`](crate::LockedBuffer)).
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.data.is_locked()
+    }
+
+    /// Returns `true` if the buffer lives in a guarded allocation (see
+    /// [`AllocMode::Guarded`]).
+    #[inline]
+    pub fn is_guarded(&self) -> bool {
+        self.data.is_guarded()
     }
 
     /// Returns the total capacity of the buffer.
     ///
+    /// For locked buffers this is the page-rounded size of the mapping.
+    ///
     /// # Examples
     ///
     /// ```
@@ -309,7 +609,7 @@ impl Buffer {
 
         // Grow Vec if needed (for with_capacity() usage)
         if len > self.data.len() {
-            self.data.resize(len, 0);
+            self.grow_storage(len)?;
         }
 
         self.len = len;
@@ -394,7 +694,7 @@ impl Buffer {
 
         // Grow Vec if needed
         if new_len > self.data.len() {
-            self.data.resize(new_len, 0);
+            self.grow_storage(new_len)?;
         }
 
         self.len = new_len;
@@ -422,7 +722,7 @@ impl Buffer {
 
         // Grow Vec if needed
         if new_pos > self.data.len() {
-            self.data.resize(new_pos, 0);
+            self.grow_storage(new_pos)?;
         }
 
         self.pos = new_pos;
@@ -562,7 +862,11 @@ impl Buffer {
         if new_size > BUF_MAX_SIZE {
             return Err(BufferError::SizeTooBig);
         }
-        self.data.resize(new_size, 0);
+        if new_size < self.data.len() {
+            self.truncate_zeroizing(new_size);
+        } else {
+            self.grow_storage(new_size)?;
+        }
         self.len = self.len.min(new_size);
         self.pos = self.pos.min(new_size);
         Ok(())
@@ -570,7 +874,13 @@ impl Buffer {
 
     /// Ensures the buffer has at least the specified additional capacity.
     ///
-    /// Similar to [`Vec::reserve`].
+    /// Similar to [`Vec::reserve`], except that if the storage has to move
+    /// the old allocation is zeroized before it is freed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is locked or guarded and the larger region
+    /// cannot be allocated (e.g. `RLIMIT_MEMLOCK` is exhausted).
     ///
     /// # Examples
     ///
@@ -583,12 +893,19 @@ impl Buffer {
     /// ```
     #[inline]
     pub fn reserve(&mut self, additional: usize) {
-        self.data.reserve(additional);
+        let needed = self.data.len().saturating_add(additional);
+        if needed > self.data.capacity() {
+            if let Err(e) = self.realloc_zeroizing(needed) {
+                panic!("Buffer reserve of {} bytes failed: {}", needed, e);
+            }
+        }
     }
 
     /// Shrinks the buffer capacity to fit the current length.
     ///
-    /// Frees unused memory.
+    /// Frees unused memory.  The discarded tail and the old allocation are
+    /// zeroized first.  For locked and guarded buffers this is best-effort:
+    /// if a smaller region cannot be allocated the current one is kept.
     ///
     /// # Examples
     ///
@@ -604,15 +921,20 @@ impl Buffer {
     /// ```
     #[inline]
     pub fn shrink_to_fit(&mut self) {
-        self.data.truncate(self.len);
-        self.data.shrink_to_fit();
+        self.truncate_zeroizing(self.len);
+        if self.data.has_excess_capacity() {
+            // Failure leaves the (already truncated) current storage intact.
+            let _ = self.realloc_zeroizing(self.data.len());
+        }
     }
 
     /// Consumes the buffer and returns the underlying `Vec<u8>`,
     /// truncated to valid data length (pos..len slice).
     ///
     /// The Vec is NOT zeroed — caller takes ownership and is responsible
-    /// for its lifetime. Use only for non-secret data (e.g. tunnel payloads).
+    /// for its lifetime. Use only for non-secret data (e.g. tunnel payloads);
+    /// use [`into_secret`](Self::into_secret) for anything sensitive.
+    /// Locked and guarded storage is copied out and then wiped and released.
     ///
     /// # Examples
     ///
@@ -625,12 +947,29 @@ impl Buffer {
     /// assert_eq!(v, b"hello");
     /// ```
     pub fn into_vec(mut self) -> Vec<u8> {
-        self.data.truncate(self.len); // trim to valid data only
-        let mut v = Vec::new();
-        std::mem::swap(&mut self.data, &mut v); // steal the allocation
-        // Now self.data is empty Vec — zeroize(drop) will zeroize nothing,
-        // and v holds the original allocation with real data
-        v
+        self.truncate_zeroizing(self.len); // trim to valid data only
+        // Steal the storage: self.data becomes an empty Vec, so zeroize(drop)
+        // will zeroize nothing.
+        core::mem::take(&mut self.data).into_vec()
+    }
+
+    /// Consumes the buffer and returns its valid data as [`SecretBytes`].
+    ///
+    /// Heap allocations are moved, not copied, and are zeroized in full
+    /// (including the bytes past `len`) when the secret is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    ///
+    /// let mut buf = Buffer::new(1024);
+    /// buf.put_bytes(b"session key").unwrap();
+    /// let key = buf.into_secret();
+    /// assert_eq!(key.expose_secret(), b"session key");
+    /// ```
+    pub fn into_secret(self) -> SecretBytes {
+        SecretBytes::new(self.into_vec())
     }
 }
 
@@ -658,8 +997,8 @@ mod tests {
         assert_eq!(buf.capacity(), 1024);
         assert_eq!(buf.len(), 0);
 
-        buf.data.extend_from_slice(b"hello");
-        buf.len = 5;
+        buf.set_len(5).unwrap();
+        buf.as_mut_slice().copy_from_slice(b"hello");
 
         assert_eq!(buf.len(), 5);
         assert_eq!(buf.as_slice(), b"hello");
@@ -692,6 +1031,110 @@ mod tests {
         assert_eq!(buf.len(), 100);
     }
 
+    #[test]
+    fn test_growable_doubles_up_to_limit() {
+        let mut buf = Buffer::growable(0, 300);
+        buf.put_u32(1).unwrap();
+        assert_eq!(buf.data.len(), MIN_GROW_SIZE);
+
+        buf.put_bytes(&[7; 100]).unwrap();
+        assert_eq!(buf.data.len(), 128);
+        assert_eq!(buf.len(), 104);
+
+        buf.put_bytes(&[8; 196]).unwrap();
+        assert_eq!(buf.data.len(), 300);
+        assert_eq!(
+            buf.put_byte(0),
+            Err(BufferError::overflow("put_byte", 300, 1, 0))
+        );
+
+        buf.set_pos(0).unwrap();
+        assert_eq!(buf.get_u32().unwrap(), 1);
+        assert_eq!(buf.get_bytes_ref(100).unwrap(), &[7; 100]);
+    }
+
+    #[test]
+    fn test_growth_uses_reserved_capacity() {
+        let mut buf = Buffer::with_capacity(256);
+        assert_eq!(buf.put_u32(1), Err(BufferError::overflow("put_u32", 0, 4, 0)));
+
+        buf.set_growth_policy(GrowthPolicy::Doubling { max: 1024 })
+            .unwrap();
+        let ptr = buf.data.as_ptr();
+        buf.put_u32(1).unwrap();
+        assert_eq!(buf.data.as_ptr(), ptr);
+        assert_eq!(buf.data.len(), 256);
+    }
+
+    #[test]
+    fn test_growth_limit_validated() {
+        let mut buf = Buffer::new(16);
+        assert_eq!(
+            buf.set_growth_policy(GrowthPolicy::Doubling {
+                max: BUF_MAX_SIZE + 1
+            }),
+            Err(BufferError::SizeTooBig)
+        );
+        assert_eq!(buf.growth_policy(), GrowthPolicy::Fixed);
+    }
+
+    #[test]
+    fn test_truncation_wipes_spare_capacity() {
+        let mut buf = Buffer::new(64);
+        buf.put_bytes(&[0x5A; 64]).unwrap();
+        buf.resize(16).unwrap();
+
+        assert_eq!(buf.data.capacity(), 64);
+        #[allow(irrefutable_let_patterns)] // `Heap` is the only variant without std
+        let Storage::Heap(vec) = &mut buf.data else {
+            unreachable!()
+        };
+        let spare = &vec.spare_capacity_mut()[..48];
+        assert!(spare.iter().all(|b| unsafe { b.assume_init() } == 0));
+    }
+
+    #[test]
+    fn test_reserve_preserves_contents() {
+        let mut buf = Buffer::new(8);
+        buf.put_bytes(b"abcdefgh").unwrap();
+        buf.reserve(100);
+        assert!(buf.capacity() >= 108);
+        assert_eq!(buf.as_slice(), b"abcdefgh");
+        assert_eq!(buf.data.len(), 8);
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    #[test]
+    fn test_guarded_buffer_round_trip_and_growth() {
+        let mut buf = Buffer::guarded(16).unwrap();
+        assert!(buf.is_guarded());
+        assert_eq!(buf.capacity(), 16);
+        buf.put_bytes(&[3; 16]).unwrap();
+        assert_eq!(
+            buf.put_byte(0),
+            Err(BufferError::overflow("put_byte", 16, 1, 0))
+        );
+
+        buf.set_growth_policy(GrowthPolicy::Doubling { max: 1024 })
+            .unwrap();
+        buf.put_bytes(&[4; 100]).unwrap();
+        assert!(buf.is_guarded());
+        assert_eq!(&buf.as_slice()[..16], &[3; 16]);
+
+        let copy = buf.clone();
+        assert!(copy.is_guarded());
+        assert_eq!(copy.as_slice(), buf.as_slice());
+    }
+
+    #[test]
+    fn test_alloc_mode_size_limit() {
+        assert!(matches!(
+            Buffer::with_alloc_mode(BUF_MAX_SIZE + 1, AllocMode::Guarded),
+            Err(BufferError::SizeTooBig)
+        ));
+        assert!(!Buffer::with_alloc_mode(8, AllocMode::Heap).unwrap().is_guarded());
+    }
+
     #[test]
     fn test_reset() {
         let mut buf = Buffer::new(1024);
@@ -0,0 +1,193 @@
+// src/buffer/ct.rs
+//! Constant-time comparison and selection on buffer contents
+
+use super::core::Buffer;
+use super::ops::MAX_STRING_LEN;
+use crate::ct;
+use crate::error::{BufferError, Result};
+
+impl Buffer {
+    /// Compares the valid data with `other` in constant time.
+    ///
+    /// Use this instead of `as_slice() == other` for MACs, tokens and other
+    /// secrets.  Only the lengths may leak through timing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(32);
+    /// buf.put_bytes(b"expected-mac")?;
+    /// assert!(buf.ct_eq(b"expected-mac"));
+    /// assert!(!buf.ct_eq(b"expected-maC"));
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        ct::ct_eq(self.as_slice(), other)
+    }
+
+    /// Compares `len` bytes of valid data starting at `pos` with `other` in
+    /// constant time.  Does not move the read position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if `pos + len` exceeds the
+    /// valid data length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_bytes(b"payload")?;
+    /// buf.put_bytes(&[0xAA; 16])?; // trailing MAC
+    /// assert!(buf.ct_eq_range(7, 16, &[0xAA; 16])?);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn ct_eq_range(&self, pos: usize, len: usize, other: &[u8]) -> Result<bool> {
+        match pos.checked_add(len) {
+            Some(end) if end <= self.len => Ok(ct::ct_eq(&self.data[pos..end], other)),
+            _ => Err(BufferError::overflow(
+                "ct_eq_range",
+                pos,
+                len,
+                self.len.saturating_sub(pos),
+            )),
+        }
+    }
+
+    /// Replaces the valid data with `other` if `choice` is `true`, leaving it
+    /// unchanged otherwise, without branching on `choice`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidState`] if `other.len()` differs from
+    /// the valid data length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::from_vec(b"real key".to_vec());
+    /// buf.ct_select(b"dummykey", false)?;
+    /// assert_eq!(buf.as_slice(), b"real key");
+    /// buf.ct_select(b"dummykey", true)?;
+    /// assert_eq!(buf.as_slice(), b"dummykey");
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn ct_select(&mut self, other: &[u8], choice: bool) -> Result<()> {
+        if other.len() != self.len {
+            return Err(BufferError::InvalidState("ct_select length mismatch"));
+        }
+        ct::ct_select(self.as_mut_slice(), other, choice);
+        Ok(())
+    }
+
+    /// Returns `true` if all valid data is zero, in constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    ///
+    /// let mut buf = Buffer::new(16);
+    /// buf.set_len(16).unwrap();
+    /// assert!(buf.ct_is_zero());
+    /// ```
+    #[inline]
+    pub fn ct_is_zero(&self) -> bool {
+        ct::ct_is_zero(self.as_slice())
+    }
+
+    /// Reads an SSH-style string and compares it with `expected` in constant
+    /// time.
+    ///
+    /// The string is consumed whether or not it matches.  Only the length of
+    /// the wire string, which is public, may leak through timing.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`get_string_ref`](Self::get_string_ref).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_string(b"s3cret-token")?;
+    /// buf.set_pos(0)?;
+    /// assert!(buf.get_string_ct_eq(b"s3cret-token")?);
+    /// assert_eq!(buf.remaining(), 0);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn get_string_ct_eq(&mut self, expected: &[u8]) -> Result<bool> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        Ok(ct::ct_eq(self.get_bytes_ref(len)?, expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq_uses_valid_data_only() {
+        let mut buf = Buffer::new(64);
+        buf.put_bytes(b"tag").unwrap();
+        assert!(buf.ct_eq(b"tag"));
+        assert!(!buf.ct_eq(b"tag\0"));
+    }
+
+    #[test]
+    fn test_ct_eq_range_bounds() {
+        let buf = Buffer::from_vec(b"0123456789".to_vec());
+        assert!(buf.ct_eq_range(2, 3, b"234").unwrap());
+        assert!(!buf.ct_eq_range(2, 3, b"235").unwrap());
+        assert!(buf.ct_eq_range(10, 0, b"").unwrap());
+        assert_eq!(
+            buf.ct_eq_range(8, 3, b"89?"),
+            Err(BufferError::overflow("ct_eq_range", 8, 3, 2))
+        );
+        assert_eq!(
+            buf.ct_eq_range(usize::MAX, 2, b"xx"),
+            Err(BufferError::overflow("ct_eq_range", usize::MAX, 2, 0))
+        );
+    }
+
+    #[test]
+    fn test_ct_select_length_mismatch() {
+        let mut buf = Buffer::from_vec(vec![1, 2, 3]);
+        assert!(buf.ct_select(&[0; 2], true).is_err());
+        assert_eq!(buf.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_get_string_ct_eq_consumes_string() {
+        let mut buf = Buffer::new(64);
+        buf.put_string(b"password").unwrap();
+        buf.put_string(b"next").unwrap();
+        buf.set_pos(0).unwrap();
+
+        assert!(!buf.get_string_ct_eq(b"passw0rd").unwrap());
+        assert!(buf.get_string_ct_eq(b"next").unwrap());
+        assert_eq!(
+            buf.get_string_ct_eq(b""),
+            Err(BufferError::overflow("get_u32", 20, 4, 0))
+        );
+    }
+}
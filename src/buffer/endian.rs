@@ -0,0 +1,173 @@
+// src/buffer/endian.rs
+//! Fixed-width integer and float accessors in both byte orders.
+//!
+//! The big-endian `u16`/`u32`/`u64` accessors used by SSH live in
+//! [`ops`](super::ops) and [`unsafe_ops`](super::unsafe_ops); everything else
+//! (little-endian, signed, 128-bit and IEEE-754 floats) is generated here so
+//! each width gets the same single-bounds-check shape as
+//! [`Buffer::put_u32`].
+
+use super::core::Buffer;
+use crate::error::Result;
+
+macro_rules! fixed_width_accessors {
+    ($(
+        $ty:ty, $size:literal, $order:literal, $to_bytes:ident, $from_bytes:ident,
+        $put:ident, $get:ident, $put_unchecked:ident, $get_unchecked:ident;
+    )*) => {
+        impl Buffer {
+            $(
+                #[doc = concat!("Writes a ", $order, " `", stringify!($ty), "` with bounds checking.")]
+                #[inline]
+                pub fn $put(&mut self, val: $ty) -> Result<()> {
+                    self.ensure_writable(stringify!($put), $size)?;
+                    unsafe {
+                        self.$put_unchecked(val);
+                    }
+                    Ok(())
+                }
+
+                #[doc = concat!("Reads a ", $order, " `", stringify!($ty), "` with bounds checking.")]
+                #[inline]
+                pub fn $get(&mut self) -> Result<$ty> {
+                    self.ensure_readable(stringify!($get), $size)?;
+                    Ok(unsafe { self.$get_unchecked() })
+                }
+
+                #[doc = concat!("Writes a ", $order, " `", stringify!($ty), "` without bounds checking.")]
+                ///
+                /// # Safety
+                ///
+                #[doc = concat!("Caller MUST guarantee: `self.pos + ", stringify!($size), " <= self.capacity()`.")]
+                #[inline(always)]
+                pub unsafe fn $put_unchecked(&mut self, val: $ty) {
+                    debug_assert!(self.pos + $size <= self.capacity());
+
+                    let ptr = unsafe { self.data.as_mut_ptr().add(self.pos) };
+                    unsafe { ptr.cast::<[u8; $size]>().write_unaligned(val.$to_bytes()) };
+                    self.pos += $size;
+                    if self.pos > self.len {
+                        self.len = self.pos;
+                    }
+                }
+
+                #[doc = concat!("Reads a ", $order, " `", stringify!($ty), "` without bounds checking.")]
+                ///
+                /// # Safety
+                ///
+                #[doc = concat!("Caller MUST guarantee: `self.pos + ", stringify!($size), " <= self.len`.")]
+                #[inline(always)]
+                pub unsafe fn $get_unchecked(&mut self) -> $ty {
+                    debug_assert!(self.pos + $size <= self.len);
+
+                    let ptr = unsafe { self.data.as_ptr().add(self.pos) };
+                    let bytes = unsafe { ptr.cast::<[u8; $size]>().read_unaligned() };
+                    self.pos += $size;
+                    <$ty>::$from_bytes(bytes)
+                }
+            )*
+        }
+    };
+}
+
+fixed_width_accessors! {
+    u16, 2, "little-endian", to_le_bytes, from_le_bytes,
+        put_u16_le, get_u16_le, put_u16_le_unchecked, get_u16_le_unchecked;
+    u32, 4, "little-endian", to_le_bytes, from_le_bytes,
+        put_u32_le, get_u32_le, put_u32_le_unchecked, get_u32_le_unchecked;
+    u64, 8, "little-endian", to_le_bytes, from_le_bytes,
+        put_u64_le, get_u64_le, put_u64_le_unchecked, get_u64_le_unchecked;
+    u128, 16, "big-endian", to_be_bytes, from_be_bytes,
+        put_u128, get_u128, put_u128_unchecked, get_u128_unchecked;
+    u128, 16, "little-endian", to_le_bytes, from_le_bytes,
+        put_u128_le, get_u128_le, put_u128_le_unchecked, get_u128_le_unchecked;
+
+    i16, 2, "big-endian", to_be_bytes, from_be_bytes,
+        put_i16, get_i16, put_i16_unchecked, get_i16_unchecked;
+    i16, 2, "little-endian", to_le_bytes, from_le_bytes,
+        put_i16_le, get_i16_le, put_i16_le_unchecked, get_i16_le_unchecked;
+    i32, 4, "big-endian", to_be_bytes, from_be_bytes,
+        put_i32, get_i32, put_i32_unchecked, get_i32_unchecked;
+    i32, 4, "little-endian", to_le_bytes, from_le_bytes,
+        put_i32_le, get_i32_le, put_i32_le_unchecked, get_i32_le_unchecked;
+    i64, 8, "big-endian", to_be_bytes, from_be_bytes,
+        put_i64, get_i64, put_i64_unchecked, get_i64_unchecked;
+    i64, 8, "little-endian", to_le_bytes, from_le_bytes,
+        put_i64_le, get_i64_le, put_i64_le_unchecked, get_i64_le_unchecked;
+    i128, 16, "big-endian", to_be_bytes, from_be_bytes,
+        put_i128, get_i128, put_i128_unchecked, get_i128_unchecked;
+    i128, 16, "little-endian", to_le_bytes, from_le_bytes,
+        put_i128_le, get_i128_le, put_i128_le_unchecked, get_i128_le_unchecked;
+
+    f32, 4, "big-endian", to_be_bytes, from_be_bytes,
+        put_f32, get_f32, put_f32_unchecked, get_f32_unchecked;
+    f32, 4, "little-endian", to_le_bytes, from_le_bytes,
+        put_f32_le, get_f32_le, put_f32_le_unchecked, get_f32_le_unchecked;
+    f64, 8, "big-endian", to_be_bytes, from_be_bytes,
+        put_f64, get_f64, put_f64_unchecked, get_f64_unchecked;
+    f64, 8, "little-endian", to_le_bytes, from_le_bytes,
+        put_f64_le, get_f64_le, put_f64_le_unchecked, get_f64_le_unchecked;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BufferError;
+
+    #[test]
+    fn test_little_endian_layout() {
+        let mut buf = Buffer::new(32);
+        buf.put_u16_le(0x0102).unwrap();
+        buf.put_u32_le(0x0304_0506).unwrap();
+        buf.put_u64_le(0x0708_090a_0b0c_0d0e).unwrap();
+        assert_eq!(
+            buf.as_slice(),
+            &[2, 1, 6, 5, 4, 3, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 9, 8, 7]
+        );
+
+        buf.set_pos(0).unwrap();
+        assert_eq!(buf.get_u16_le().unwrap(), 0x0102);
+        assert_eq!(buf.get_u32_le().unwrap(), 0x0304_0506);
+        assert_eq!(buf.get_u64_le().unwrap(), 0x0708_090a_0b0c_0d0e);
+    }
+
+    #[test]
+    fn test_mixed_round_trip() {
+        let mut buf = Buffer::new(128);
+        buf.put_u128(u128::MAX - 1).unwrap();
+        buf.put_u128_le(0x1234).unwrap();
+        buf.put_i16(-2).unwrap();
+        buf.put_i32_le(i32::MIN).unwrap();
+        buf.put_i64(-1).unwrap();
+        buf.put_i128_le(i128::MIN + 7).unwrap();
+        buf.put_f32(1.5).unwrap();
+        buf.put_f64_le(-0.25).unwrap();
+
+        buf.set_pos(0).unwrap();
+        assert_eq!(buf.get_u128().unwrap(), u128::MAX - 1);
+        assert_eq!(buf.get_u128_le().unwrap(), 0x1234);
+        assert_eq!(buf.get_i16().unwrap(), -2);
+        assert_eq!(buf.get_i32_le().unwrap(), i32::MIN);
+        assert_eq!(buf.get_i64().unwrap(), -1);
+        assert_eq!(buf.get_i128_le().unwrap(), i128::MIN + 7);
+        assert_eq!(buf.get_f32().unwrap(), 1.5);
+        assert_eq!(buf.get_f64_le().unwrap(), -0.25);
+        assert_eq!(buf.remaining(), 0);
+    }
+
+    #[test]
+    fn test_bounds_checked() {
+        let mut buf = Buffer::new(7);
+        assert_eq!(
+            buf.put_f64(1.0),
+            Err(BufferError::overflow("put_f64", 0, 8, 7))
+        );
+        buf.put_i32_le(-5).unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(
+            buf.get_u64_le(),
+            Err(BufferError::overflow("get_u64_le", 0, 8, 4))
+        );
+        assert_eq!(buf.get_i32_le().unwrap(), -5);
+    }
+}
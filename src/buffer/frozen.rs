@@ -0,0 +1,282 @@
+// src/buffer/frozen.rs
+//! Immutable, reference-counted views of a [`Buffer`]'s contents.
+
+use super::core::Buffer;
+use crate::error::{BufferError, Result};
+#[cfg(all(unix, feature = "std"))]
+use crate::pool::PooledLockedBuffer;
+#[cfg(feature = "std")]
+use crate::pool::{FastPooledBuffer, PooledBuffer};
+use core::ops::{Bound, RangeBounds};
+use alloc::sync::Arc;
+
+/// The allocation shared by all views of one frozen buffer.
+///
+/// Dropping it runs the owner's own cleanup: a plain [`Buffer`] zeroizes
+/// itself, and pooled buffers are burned and returned to their pool.
+pub(crate) enum Owner {
+    Plain(Buffer),
+    #[cfg(feature = "std")]
+    Pooled(PooledBuffer),
+    #[cfg(feature = "std")]
+    FastPooled(FastPooledBuffer),
+    #[cfg(all(unix, feature = "std"))]
+    Locked(PooledLockedBuffer),
+}
+
+impl Owner {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Plain(buf) => buf.as_slice(),
+            #[cfg(feature = "std")]
+            Self::Pooled(buf) => buf.as_slice(),
+            #[cfg(feature = "std")]
+            Self::FastPooled(buf) => buf.as_slice(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(buf) => buf.as_slice(),
+        }
+    }
+}
+
+/// A read-only, reference-counted view of a frozen [`Buffer`].
+///
+/// Created with [`Buffer::freeze`] (or `freeze` on a pooled buffer).  Views
+/// are `Send + Sync`, and cloning, [`slice`](Self::slice),
+/// [`split_to`](Self::split_to) and [`split_off`](Self::split_off) are O(1):
+/// they share the original allocation instead of copying it, so fanning a
+/// packet out to several consumers does not duplicate its contents.
+///
+/// The allocation is zeroized — or burned and returned to its pool — when
+/// the last view drops.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::Buffer;
+/// # use secbuf::BufferError;
+///
+/// let mut buf = Buffer::new(64);
+/// buf.put_bytes(b"headerpayload")?;
+///
+/// let mut packet = buf.freeze();
+/// let header = packet.split_to(6)?;
+/// assert_eq!(&header[..], b"header");
+/// assert_eq!(&packet[..], b"payload");
+///
+/// let copy = packet.clone(); // shares the allocation
+/// assert_eq!(copy.as_ptr(), packet.as_ptr());
+/// # Ok::<(), BufferError>(())
+/// ```
+#[derive(Clone)]
+pub struct FrozenBuffer {
+    owner: Arc<Owner>,
+    start: usize,
+    end: usize,
+}
+
+impl FrozenBuffer {
+    pub(crate) fn from_owner(owner: Owner) -> Self {
+        let end = owner.as_slice().len();
+        Self {
+            owner: Arc::new(owner),
+            start: 0,
+            end,
+        }
+    }
+
+    /// The bytes visible through this view.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.owner.as_slice()[self.start..self.end]
+    }
+
+    /// Length of this view in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if this view is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns a new view of `range` within this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if the range is out of bounds
+    /// or decreasing.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Result<Self> {
+        // Saturating: a bound of `usize::MAX` is out of range either way.
+        let begin = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+        self.view("slice", begin, end)
+    }
+
+    fn view(&self, op: &'static str, begin: usize, end: usize) -> Result<Self> {
+        if begin > end || end > self.len() {
+            return Err(BufferError::overflow(
+                op,
+                begin,
+                end.saturating_sub(begin),
+                self.len().saturating_sub(begin),
+            ));
+        }
+        Ok(Self {
+            owner: Arc::clone(&self.owner),
+            start: self.start + begin,
+            end: self.start + end,
+        })
+    }
+
+    /// Splits off and returns the first `at` bytes; `self` keeps the rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if `at > len()`.
+    pub fn split_to(&mut self, at: usize) -> Result<Self> {
+        let head = self.view("split_to", 0, at)?;
+        self.start += at;
+        Ok(head)
+    }
+
+    /// Splits off and returns the bytes from `at` onwards; `self` keeps the
+    /// first `at` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if `at > len()`.
+    pub fn split_off(&mut self, at: usize) -> Result<Self> {
+        let tail = self.view("split_off", at, self.len())?;
+        self.end = self.start + at;
+        Ok(tail)
+    }
+
+    /// Number of views currently sharing the allocation, including this one.
+    #[inline]
+    pub fn ref_count(&self) -> usize {
+        Arc::strong_count(&self.owner)
+    }
+}
+
+impl core::ops::Deref for FrozenBuffer {
+    type Target = [u8];
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for FrozenBuffer {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl From<Buffer> for FrozenBuffer {
+    fn from(buf: Buffer) -> Self {
+        buf.freeze()
+    }
+}
+
+impl Buffer {
+    /// Converts the buffer into a read-only, reference-counted
+    /// [`FrozenBuffer`] covering its valid data (`0..len`).
+    ///
+    /// The storage is moved, not copied, and is zeroized when the last view
+    /// drops.
+    #[inline]
+    pub fn freeze(self) -> FrozenBuffer {
+        FrozenBuffer::from_owner(Owner::Plain(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    #[cfg(feature = "std")]
+    use crate::pool::{BufferPool, PoolConfig};
+
+    fn frozen(bytes: &[u8]) -> FrozenBuffer {
+        Buffer::from_vec(bytes.to_vec()).freeze()
+    }
+
+    #[test]
+    fn test_frozen_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<FrozenBuffer>();
+    }
+
+    #[test]
+    fn test_slice_bounds() {
+        let buf = frozen(b"0123456789");
+        assert_eq!(&buf.slice(2..5).unwrap()[..], b"234");
+        assert_eq!(&buf.slice(..=1).unwrap()[..], b"01");
+        assert_eq!(&buf.slice(8..).unwrap()[..], b"89");
+        assert!(buf.slice(10..).unwrap().is_empty());
+        assert_eq!(
+            buf.slice(5..11).err(),
+            Some(BufferError::overflow("slice", 5, 6, 5))
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = buf.slice(6..5);
+        assert_eq!(reversed.err().unwrap().kind(), ErrorKind::BufferOverflow);
+        assert!(buf.slice(..=usize::MAX).is_err());
+
+        // Nested slices are relative to their parent view.
+        let inner = buf.slice(2..8).unwrap().slice(1..3).unwrap();
+        assert_eq!(&inner[..], b"34");
+    }
+
+    #[test]
+    fn test_split_to_and_split_off() {
+        let mut buf = frozen(b"headerbodytrailer");
+        let header = buf.split_to(6).unwrap();
+        let trailer = buf.split_off(4).unwrap();
+        assert_eq!(&header[..], b"header");
+        assert_eq!(&buf[..], b"body");
+        assert_eq!(&trailer[..], b"trailer");
+        assert_eq!(buf.ref_count(), 3);
+
+        assert!(buf.split_to(5).is_err());
+        assert_eq!(&buf[..], b"body");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pooled_buffer_returns_after_last_view() {
+        let pool = BufferPool::new(PoolConfig {
+            buffer_size: 64,
+            max_pool_size: 4,
+            min_pool_size: 0,
+            ..PoolConfig::default()
+        });
+        let mut buf = pool.acquire();
+        buf.put_bytes(b"fan-out packet").unwrap();
+
+        let a = buf.freeze();
+        let b = a.slice(..6).unwrap();
+        drop(a);
+        assert_eq!(pool.available(), 0);
+        assert_eq!(&b[..], b"fan-ou");
+
+        drop(b);
+        assert_eq!(pool.available(), 1);
+        let mut reused = pool.acquire();
+        reused.set_len(14).unwrap();
+        assert_eq!(reused.as_slice(), &[0u8; 14]);
+    }
+}
@@ -0,0 +1,145 @@
+// src/buffer/io.rs
+//! `std::io` trait implementations for [`Buffer`].
+//!
+//! The traits share the buffer's single cursor:
+//!
+//! - [`Write`](io::Write) writes at `pos` and extends `len`, growing the
+//!   storage if the [`GrowthPolicy`](super::GrowthPolicy) allows.  A full
+//!   buffer accepts a short write and then `Ok(0)`, so `write_all` reports
+//!   [`io::ErrorKind::WriteZero`].
+//! - [`Read`](io::Read) and [`BufRead`](io::BufRead) consume `pos..len`.
+//! - [`Seek`](io::Seek) moves `pos` within `0..=len`; seeking outside the
+//!   valid region is an [`io::ErrorKind::InvalidInput`] error.
+
+use super::core::Buffer;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+impl Write for Buffer {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        let n = src.len().min(self.writable_room());
+        self.ensure_writable("write", n)?;
+        unsafe {
+            self.put_bytes_unchecked(&src[..n]);
+        }
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for Buffer {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let n = out.len().min(self.remaining());
+        out[..n].copy_from_slice(unsafe { self.get_bytes_unchecked(n) });
+        Ok(n)
+    }
+}
+
+impl BufRead for Buffer {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&self.data[self.pos..self.len])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = self.pos.saturating_add(amt).min(self.len);
+    }
+}
+
+impl Seek for Buffer {
+    fn seek(&mut self, from: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match from {
+            SeekFrom::Start(n) => (0, i64::try_from(n).unwrap_or(i64::MAX)),
+            SeekFrom::End(n) => (self.len, n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+        let target = (base as i64)
+            .checked_add(offset)
+            .filter(|&t| t >= 0 && t as usize <= self.len)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "seek outside the buffer's valid data",
+                )
+            })?;
+        self.pos = target as usize;
+        Ok(self.pos as u64)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.pos as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_extends_len_and_stops_when_full() {
+        let mut buf = Buffer::new(8);
+        assert_eq!(buf.write(b"hello").unwrap(), 5);
+        assert_eq!(buf.write(b"world").unwrap(), 3);
+        assert_eq!(buf.write(b"!").unwrap(), 0);
+        assert_eq!(buf.as_slice(), b"hellowor");
+
+        let err = buf.write_all(b"x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_write_grows_when_allowed() {
+        let mut buf = Buffer::growable(4, 100);
+        buf.write_all(&[1; 64]).unwrap();
+        assert_eq!(buf.len(), 64);
+        assert_eq!(buf.write(&[2; 64]).unwrap(), 36);
+        assert_eq!(buf.len(), 100);
+    }
+
+    #[test]
+    fn test_read_and_buf_read() {
+        let mut buf = Buffer::from_vec(b"line one\nline two\n".to_vec());
+        let mut line = String::new();
+        buf.read_line(&mut line).unwrap();
+        assert_eq!(line, "line one\n");
+        assert_eq!(buf.fill_buf().unwrap(), b"line two\n");
+
+        let mut rest = [0u8; 32];
+        assert_eq!(buf.read(&mut rest).unwrap(), 9);
+        assert_eq!(buf.read(&mut rest).unwrap(), 0);
+
+        buf.set_pos(0).unwrap();
+        buf.consume(usize::MAX);
+        assert_eq!(buf.pos(), buf.len());
+    }
+
+    #[test]
+    fn test_seek_bounded_to_valid_region() {
+        let mut buf = Buffer::new(64);
+        buf.write_all(b"0123456789").unwrap();
+
+        assert_eq!(buf.seek(SeekFrom::Start(2)).unwrap(), 2);
+        assert_eq!(buf.seek(SeekFrom::Current(3)).unwrap(), 5);
+        assert_eq!(buf.seek(SeekFrom::End(-1)).unwrap(), 9);
+        assert_eq!(buf.seek(SeekFrom::End(0)).unwrap(), 10);
+
+        for bad in [SeekFrom::Start(11), SeekFrom::End(1), SeekFrom::Current(-11)] {
+            let err = buf.seek(bad).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+        assert_eq!(buf.pos(), 10);
+    }
+
+    #[test]
+    fn test_io_copy_round_trip() {
+        let mut src = Buffer::from_vec(vec![0xC3; 300]);
+        let mut dst = Buffer::growable(0, 1024);
+        assert_eq!(io::copy(&mut src, &mut dst).unwrap(), 300);
+        assert_eq!(dst.as_slice(), &[0xC3; 300][..]);
+    }
+}
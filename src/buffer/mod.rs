@@ -2,7 +2,33 @@
 //! High-performance buffer implementation
 
 pub mod core;
+pub(crate) mod array;
+#[cfg(feature = "bytes")]
+pub(crate) mod bytes_compat;
+pub(crate) mod checkpoint;
+pub(crate) mod codec;
+pub(crate) mod ct;
+pub(crate) mod endian;
+pub(crate) mod frozen;
+#[cfg(feature = "std")]
+pub(crate) mod io;
 pub(crate) mod ops;
+pub(crate) mod prefix;
+pub(crate) mod ssh;
+pub(crate) mod storage;
+pub(crate) mod stream;
+pub(crate) mod sub_reader;
+pub(crate) mod traits;
 pub(crate) mod unsafe_ops;
+pub(crate) mod varint;
 
-pub use core::Buffer;
+pub use array::ArrayBuffer;
+pub use checkpoint::Checkpoint;
+pub use codec::{Decode, Encode};
+pub use core::{AllocMode, Buffer, GrowthPolicy};
+pub use frozen::FrozenBuffer;
+pub use prefix::{LengthPrefixed, PrefixWidth};
+pub use stream::{StreamBuffer, StreamReader, StreamWriter};
+pub use sub_reader::SubReader;
+pub use traits::{BufferRead, BufferWrite};
+pub use varint::QUIC_VARINT_MAX;
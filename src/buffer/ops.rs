@@ -3,17 +3,34 @@
 
 use super::core::Buffer;
 use crate::error::{BufferError, Result};
+use crate::secret::SecretBytes;
+use alloc::vec::Vec;
 
 /// Maximum SSH-style string length
-const MAX_STRING_LEN: usize = 400_000;
+pub(crate) const MAX_STRING_LEN: usize = 400_000;
 
 impl Buffer {
+    /// Writes a `u16` in big-endian format with bounds checking.
+    #[inline]
+    pub fn put_u16(&mut self, val: u16) -> Result<()> {
+        self.ensure_writable("put_u16", 2)?;
+        unsafe {
+            self.put_u16_unchecked(val);
+        }
+        Ok(())
+    }
+
+    /// Reads a `u16` in big-endian format with bounds checking.
+    #[inline]
+    pub fn get_u16(&mut self) -> Result<u16> {
+        self.ensure_readable("get_u16", 2)?;
+        Ok(unsafe { self.get_u16_unchecked() })
+    }
+
     /// Writes a `u32` in big-endian format with bounds checking.
     #[inline]
     pub fn put_u32(&mut self, val: u32) -> Result<()> {
-        if self.pos + 4 > self.data.len() {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_writable("put_u32", 4)?;
         unsafe {
             self.put_u32_unchecked(val);
         }
@@ -23,18 +40,14 @@ impl Buffer {
     /// Reads a `u32` in big-endian format with bounds checking.
     #[inline]
     pub fn get_u32(&mut self) -> Result<u32> {
-        if self.pos + 4 > self.len {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_readable("get_u32", 4)?;
         Ok(unsafe { self.get_u32_unchecked() })
     }
 
     /// Writes a `u64` in big-endian format with bounds checking.
     #[inline]
     pub fn put_u64(&mut self, val: u64) -> Result<()> {
-        if self.pos + 8 > self.data.len() {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_writable("put_u64", 8)?;
         unsafe {
             self.put_u64_unchecked(val);
         }
@@ -44,18 +57,14 @@ impl Buffer {
     /// Reads a `u64` in big-endian format with bounds checking.
     #[inline]
     pub fn get_u64(&mut self) -> Result<u64> {
-        if self.pos + 8 > self.len {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_readable("get_u64", 8)?;
         Ok(unsafe { self.get_u64_unchecked() })
     }
 
     /// Writes bytes with a single bounds check.
     #[inline]
     pub fn put_bytes(&mut self, bytes: &[u8]) -> Result<()> {
-        if self.pos + bytes.len() > self.data.len() {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_writable("put_bytes", bytes.len())?;
         unsafe {
             self.put_bytes_unchecked(bytes);
         }
@@ -63,46 +72,66 @@ impl Buffer {
     }
 
     /// Reads bytes, returning an owned `Vec`.
+    ///
+    /// The returned `Vec` is **not** zeroized on drop.  Use
+    /// [`get_bytes_secret`](Self::get_bytes_secret) for key material, or
+    /// [`get_bytes_ref`](Self::get_bytes_ref) to avoid the copy entirely.
     #[inline]
     pub fn get_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
-        if self.pos + len > self.len {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_readable("get_bytes", len)?;
         let bytes = unsafe { self.get_bytes_unchecked(len) };
         Ok(bytes.to_vec())
     }
 
+    /// Reads bytes into a [`SecretBytes`] that is zeroized on drop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::from_vec(vec![0x13, 0x37, 0xca, 0xfe]);
+    /// let key = buf.get_bytes_secret(4)?;
+    /// assert_eq!(key.expose_secret(), &[0x13, 0x37, 0xca, 0xfe]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn get_bytes_secret(&mut self, len: usize) -> Result<SecretBytes> {
+        Ok(SecretBytes::from_slice(self.get_bytes_ref(len)?))
+    }
+
     /// Reads bytes as a slice reference (zero-copy).
     #[inline]
     pub fn get_bytes_ref(&mut self, len: usize) -> Result<&[u8]> {
-        if self.pos + len > self.len {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_readable("get_bytes_ref", len)?;
         Ok(unsafe { self.get_bytes_unchecked(len) })
     }
 
     /// Gets a reference to data at current position without advancing.
     pub fn get_ptr(&self, len: usize) -> Result<&[u8]> {
-        if len > 1_000_000_000 || self.pos + len > self.len {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_readable("get_ptr", len)?;
         Ok(&self.data[self.pos..self.pos + len])
     }
 
     /// Gets a mutable reference to data at current position (for writing).
     pub fn get_write_ptr(&mut self, len: usize) -> Result<&mut [u8]> {
-        if len > 1_000_000_000 || self.pos + len > self.data.len() {
-            return Err(BufferError::BufferOverflow);
+        if len > 1_000_000_000 {
+            return Err(BufferError::overflow(
+                "get_write_ptr",
+                self.pos,
+                len,
+                self.writable_room(),
+            ));
         }
+        self.ensure_writable("get_write_ptr", len)?;
         Ok(&mut self.data[self.pos..self.pos + len])
     }
 
     /// Writes a single byte.
     #[inline]
     pub fn put_byte(&mut self, val: u8) -> Result<()> {
-        if self.pos >= self.data.len() {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_writable("put_byte", 1)?;
         self.data[self.pos] = val;
         self.pos += 1;
         if self.pos > self.len {
@@ -114,14 +143,31 @@ impl Buffer {
     /// Reads a single byte.
     #[inline]
     pub fn get_byte(&mut self) -> Result<u8> {
-        if self.pos >= self.len {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_readable("get_byte", 1)?;
         let byte = self.data[self.pos];
         self.pos += 1;
         Ok(byte)
     }
 
+    /// Writes a `u8`. Alias of [`put_byte`](Self::put_byte) for symmetry with
+    /// the wider integer accessors.
+    #[inline]
+    pub fn put_u8(&mut self, val: u8) -> Result<()> {
+        self.put_byte(val)
+    }
+
+    /// Reads a `u8`. Alias of [`get_byte`](Self::get_byte).
+    #[inline]
+    pub fn get_u8(&mut self) -> Result<u8> {
+        self.get_byte()
+    }
+
+    /// Writes an SSH `boolean` (RFC 4251 §5): `1` for true, `0` for false.
+    #[inline]
+    pub fn put_bool(&mut self, val: bool) -> Result<()> {
+        self.put_byte(val as u8)
+    }
+
     /// Reads a boolean (0 = false, non-zero = true).
     #[inline]
     pub fn get_bool(&mut self) -> Result<bool> {
@@ -139,6 +185,10 @@ impl Buffer {
     }
 
     /// Reads an SSH-style string (4-byte length prefix + data).
+    ///
+    /// The returned `Vec` is **not** zeroized on drop.  Use
+    /// [`get_string_secret`](Self::get_string_secret) for passwords and keys,
+    /// or [`get_string_ref`](Self::get_string_ref) to avoid the copy entirely.
     #[inline]
     pub fn get_string(&mut self) -> Result<Vec<u8>> {
         let len = self.get_u32()? as usize;
@@ -148,6 +198,75 @@ impl Buffer {
         self.get_bytes(len)
     }
 
+    /// Reads an SSH-style string into a [`SecretBytes`] that is zeroized on drop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_string(b"correct horse")?;
+    /// buf.set_pos(0)?;
+    /// assert_eq!(buf.get_string_secret()?.expose_secret(), b"correct horse");
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn get_string_secret(&mut self) -> Result<SecretBytes> {
+        Ok(SecretBytes::from_slice(self.get_string_ref()?))
+    }
+
+    /// Reads an SSH-style string as a slice reference (zero-copy).
+    ///
+    /// Unlike [`get_string`](Self::get_string) this never allocates, so no
+    /// unzeroized copy of the payload is created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_string(b"ssh-ed25519")?;
+    /// buf.set_pos(0)?;
+    /// assert_eq!(buf.get_string_ref()?, b"ssh-ed25519");
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn get_string_ref(&mut self) -> Result<&[u8]> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        self.get_bytes_ref(len)
+    }
+
+    /// Reads an SSH-style string as a UTF-8 `&str` (zero-copy).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if the payload is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_string(b"ssh-userauth")?;
+    /// buf.set_pos(0)?;
+    /// assert_eq!(buf.get_str_ref()?, "ssh-userauth");
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn get_str_ref(&mut self) -> Result<&str> {
+        let bytes = self.get_string_ref()?;
+        core::str::from_utf8(bytes).map_err(|_| BufferError::InvalidString)
+    }
+
     /// Skips over an SSH-style string without reading the data.
     #[inline]
     pub fn eat_string(&mut self) -> Result<()> {
@@ -166,11 +285,15 @@ impl Buffer {
             return self.put_bytes(bytes);
         }
 
-        if self.pos + bytes.len() > self.data.len() {
-            return Err(BufferError::BufferOverflow);
-        }
+        self.ensure_writable("put_bytes_fast", bytes.len())?;
+
+        // Runtime detection needs std; otherwise rely on the build target.
+        #[cfg(feature = "std")]
+        let avx2 = is_x86_feature_detected!("avx2");
+        #[cfg(not(feature = "std"))]
+        let avx2 = cfg!(target_feature = "avx2");
 
-        if is_x86_feature_detected!("avx2") {
+        if avx2 {
             unsafe {
                 self.put_bytes_avx2_impl(bytes);
             }
@@ -186,7 +309,7 @@ impl Buffer {
     #[cfg(target_arch = "x86_64")]
     #[inline]
     unsafe fn put_bytes_avx2_impl(&mut self, bytes: &[u8]) {
-        use std::arch::x86_64::*;
+        use core::arch::x86_64::*;
 
         let mut src = bytes.as_ptr();
         let mut dst = unsafe { self.data.as_mut_ptr().add(self.pos) };
@@ -200,7 +323,7 @@ impl Buffer {
             remaining -= 32;
         }
 
-        unsafe { std::ptr::copy_nonoverlapping(src, dst, remaining) };
+        unsafe { core::ptr::copy_nonoverlapping(src, dst, remaining) };
 
         self.pos += bytes.len();
         if self.pos > self.len {
@@ -208,6 +331,8 @@ impl Buffer {
         }
     }
 
+    /// Bulk copy; the same as [`put_bytes`](Self::put_bytes) on targets
+    /// without a SIMD path.
     #[cfg(not(target_arch = "x86_64"))]
     #[inline]
     pub fn put_bytes_fast(&mut self, bytes: &[u8]) -> Result<()> {
@@ -0,0 +1,259 @@
+// src/buffer/prefix.rs
+//! Deferred length prefixes for nested encodings
+
+use super::checkpoint::Checkpoint;
+use super::core::Buffer;
+use super::ops::MAX_STRING_LEN;
+use crate::error::{BufferError, Result};
+
+/// Width of a big-endian length prefix written by
+/// [`Buffer::begin_length_prefixed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixWidth {
+    /// 1-byte length
+    U8,
+    /// 2-byte length
+    U16,
+    /// 4-byte length, as used by SSH strings and packets
+    U32,
+    /// 8-byte length
+    U64,
+}
+
+impl PrefixWidth {
+    /// Number of bytes the prefix occupies.
+    #[inline]
+    pub const fn bytes(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+            Self::U64 => 8,
+        }
+    }
+
+    /// Largest length the prefix can encode.
+    #[inline]
+    pub const fn max_len(self) -> u64 {
+        match self {
+            Self::U8 => u8::MAX as u64,
+            Self::U16 => u16::MAX as u64,
+            Self::U32 => u32::MAX as u64,
+            Self::U64 => u64::MAX,
+        }
+    }
+}
+
+/// A length prefix that is filled in once its payload has been written.
+///
+/// Returned by [`Buffer::begin_length_prefixed`].  The guard dereferences to
+/// the buffer; the payload is everything from the end of the prefix to the
+/// buffer's length when [`finish`](Self::finish) is called.  Guards nest, so
+/// an SSH packet containing strings of not-yet-known length can be built in
+/// one pass.
+///
+/// Dropping the guard without calling `finish` — or `finish` failing —
+/// removes the prefix and the partial payload from the buffer and zeroizes
+/// them, exactly like a rolled-back [`Checkpoint`].
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::{Buffer, PrefixWidth};
+/// # use secbuf::BufferError;
+///
+/// let mut buf = Buffer::new(64);
+/// let mut outer = buf.begin_length_prefixed(PrefixWidth::U32)?;
+/// outer.put_byte(50)?; // SSH_MSG_USERAUTH_REQUEST
+/// {
+///     let mut user = outer.begin_length_prefixed(PrefixWidth::U32)?;
+///     user.put_bytes(b"alice")?;
+///     user.finish()?;
+/// }
+/// outer.finish()?;
+///
+/// assert_eq!(buf.as_slice(), b"\0\0\0\x0a\x32\0\0\0\x05alice");
+/// # Ok::<(), BufferError>(())
+/// ```
+#[must_use = "dropping a LengthPrefixed guard discards its payload"]
+pub struct LengthPrefixed<'a> {
+    cp: Checkpoint<'a>,
+    /// Offset of the prefix itself.
+    start: usize,
+    width: PrefixWidth,
+}
+
+impl LengthPrefixed<'_> {
+    /// Number of payload bytes written so far, counted up to the buffer's
+    /// length rather than its position.
+    #[inline]
+    pub fn payload_len(&self) -> usize {
+        self.cp
+            .len()
+            .saturating_sub(self.start + self.width.bytes())
+    }
+
+    /// Writes the payload length into the reserved prefix and keeps the
+    /// encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if the payload exceeds the
+    /// SSH string limit (400 000 bytes),
+    /// [`BufferError::LengthPrefixOverflow`] if it does not fit in the prefix
+    /// width, or [`BufferError::InvalidState`] if the buffer was truncated
+    /// into the prefix.  The prefix and payload are rolled back in all cases.
+    pub fn finish(mut self) -> Result<()> {
+        let width = self.width.bytes();
+        let start = self.start;
+        if start + width > self.cp.len() {
+            return Err(BufferError::InvalidState(
+                "length prefix was truncated before finish",
+            ));
+        }
+        let len = self.payload_len();
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        if len as u64 > self.width.max_len() {
+            return Err(BufferError::LengthPrefixOverflow {
+                len,
+                width: self.width.bytes(),
+            });
+        }
+        let bytes = (len as u64).to_be_bytes();
+        self.cp.data[start..start + width].copy_from_slice(&bytes[8 - width..]);
+        self.cp.commit();
+        Ok(())
+    }
+}
+
+impl core::ops::Deref for LengthPrefixed<'_> {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        &self.cp
+    }
+}
+
+impl core::ops::DerefMut for LengthPrefixed<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cp
+    }
+}
+
+impl Buffer {
+    /// Reserves a `width`-byte length prefix at the current position and
+    /// returns a guard that backpatches it with the payload length on
+    /// [`finish`](LengthPrefixed::finish).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if there is no room for the
+    /// prefix.
+    pub fn begin_length_prefixed(&mut self, width: PrefixWidth) -> Result<LengthPrefixed<'_>> {
+        let start = self.pos;
+        let mut cp = self.checkpoint();
+        cp.put_bytes(&[0u8; 8][..width.bytes()])?;
+        Ok(LengthPrefixed { cp, start, width })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_widths() {
+        for (width, expected) in [
+            (PrefixWidth::U8, &[3][..]),
+            (PrefixWidth::U16, &[0, 3]),
+            (PrefixWidth::U32, &[0, 0, 0, 3]),
+            (PrefixWidth::U64, &[0, 0, 0, 0, 0, 0, 0, 3]),
+        ] {
+            let mut buf = Buffer::new(16);
+            let mut p = buf.begin_length_prefixed(width).unwrap();
+            p.put_bytes(b"abc").unwrap();
+            assert_eq!(p.payload_len(), 3);
+            p.finish().unwrap();
+            assert_eq!(&buf.as_slice()[..width.bytes()], expected);
+            assert_eq!(&buf.as_slice()[width.bytes()..], b"abc");
+        }
+    }
+
+    #[test]
+    fn test_matches_put_string() {
+        let mut expected = Buffer::new(32);
+        expected.put_string(b"ssh-connection").unwrap();
+
+        let mut buf = Buffer::new(32);
+        let mut p = buf.begin_length_prefixed(PrefixWidth::U32).unwrap();
+        p.put_bytes(b"ssh-connection").unwrap();
+        p.finish().unwrap();
+        assert_eq!(buf.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_width_overflow_rolls_back() {
+        let mut buf = Buffer::new(512);
+        buf.put_byte(0xEE).unwrap();
+        let mut p = buf.begin_length_prefixed(PrefixWidth::U8).unwrap();
+        p.put_bytes(&[0xAA; 256]).unwrap();
+        assert_eq!(
+            p.finish(),
+            Err(BufferError::LengthPrefixOverflow { len: 256, width: 1 })
+        );
+        assert_eq!(buf.as_slice(), &[0xEE]);
+        buf.set_len(258).unwrap();
+        assert!(buf.as_slice()[1..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_string_limit_enforced() {
+        let mut buf = Buffer::growable(16, MAX_STRING_LEN + 64);
+        let mut p = buf.begin_length_prefixed(PrefixWidth::U64).unwrap();
+        p.set_len(8 + MAX_STRING_LEN + 1).unwrap();
+        p.set_pos(8 + MAX_STRING_LEN + 1).unwrap();
+        assert_eq!(p.finish(), Err(BufferError::InvalidString));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_payload_counts_to_len_not_pos() {
+        let mut buf = Buffer::new(16);
+        let mut p = buf.begin_length_prefixed(PrefixWidth::U32).unwrap();
+        p.put_bytes(b"payload").unwrap();
+        p.set_pos(4).unwrap();
+        assert_eq!(p.payload_len(), 7);
+        p.finish().unwrap();
+        assert_eq!(buf.as_slice(), b"\0\0\0\x07payload");
+    }
+
+    #[test]
+    fn test_truncated_prefix_is_invalid_state() {
+        let mut buf = Buffer::new(16);
+        buf.put_byte(0xEE).unwrap();
+        let mut p = buf.begin_length_prefixed(PrefixWidth::U32).unwrap();
+        p.put_bytes(b"abc").unwrap();
+        p.resize(3).unwrap();
+        assert!(matches!(p.finish(), Err(BufferError::InvalidState(_))));
+        assert_eq!(buf.as_slice(), &[0xEE]);
+    }
+
+    #[test]
+    fn test_drop_without_finish_discards() {
+        let mut buf = Buffer::new(16);
+        {
+            let mut p = buf.begin_length_prefixed(PrefixWidth::U16).unwrap();
+            p.put_bytes(b"partial").unwrap();
+        }
+        assert!(buf.is_empty());
+        assert_eq!(buf.pos(), 0);
+    }
+
+    #[test]
+    fn test_no_room_for_prefix() {
+        let mut buf = Buffer::new(3);
+        assert!(buf.begin_length_prefixed(PrefixWidth::U32).is_err());
+        assert!(buf.is_empty());
+    }
+}
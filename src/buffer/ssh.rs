@@ -0,0 +1,287 @@
+// src/buffer/ssh.rs
+//! SSH wire-format primitives (RFC 4251 §5).
+//!
+//! `mpint` and `name-list` have encoding rules beyond a plain length-prefixed
+//! string, so they get dedicated, validating accessors here.  Malformed
+//! input is rejected with [`BufferError::InvalidData`] rather than being
+//! silently normalised — a peer that sends a non-minimal `mpint` or an empty
+//! `name-list` element is violating the spec.
+
+use super::core::Buffer;
+use super::ops::MAX_STRING_LEN;
+use crate::error::{BufferError, Result};
+use crate::secret::SecretBytes;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Returns `true` if `name` is a valid `name-list` element: non-empty,
+/// printable US-ASCII, and free of commas.
+#[inline]
+fn is_valid_name(name: &[u8]) -> bool {
+    !name.is_empty() && name.iter().all(|&b| (0x21..=0x7e).contains(&b) && b != b',')
+}
+
+impl Buffer {
+    /// Writes a non-negative `mpint` from its big-endian magnitude.
+    ///
+    /// Leading zero bytes are stripped and a single `0x00` is prepended when
+    /// the most significant bit is set, so the output is always the minimal
+    /// two's-complement encoding.  An all-zero (or empty) magnitude encodes
+    /// as a zero-length string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if the encoding would exceed the
+    /// SSH string limit, or [`BufferError::BufferOverflow`] if it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_mpint(&[0x80])?;
+    /// assert_eq!(buf.as_slice(), &[0, 0, 0, 2, 0x00, 0x80]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn put_mpint(&mut self, magnitude: &[u8]) -> Result<()> {
+        let start = magnitude
+            .iter()
+            .position(|&b| b != 0)
+            .unwrap_or(magnitude.len());
+        let digits = &magnitude[start..];
+        let pad = digits.first().is_some_and(|&b| b & 0x80 != 0);
+        let len = digits.len() + pad as usize;
+
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        self.ensure_writable("put_mpint", 4 + len)?;
+        unsafe {
+            self.put_u32_unchecked(len as u32);
+            if pad {
+                self.put_bytes_unchecked(&[0]);
+            }
+            self.put_bytes_unchecked(digits);
+        }
+        Ok(())
+    }
+
+    /// Reads a non-negative `mpint`, returning its big-endian magnitude.
+    ///
+    /// The result is a [`SecretBytes`] because `mpint`s routinely carry
+    /// key-exchange secrets.  Zero is returned as an empty secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidData`] if the value is negative or not
+    /// minimally encoded (a redundant leading `0x00` or `0xff`), and
+    /// [`BufferError::InvalidString`] if the length exceeds the SSH string
+    /// limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_mpint(&[0x00, 0x9a, 0x37])?;
+    /// buf.set_pos(0)?;
+    /// assert_eq!(buf.get_mpint()?.expose_secret(), &[0x9a, 0x37]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn get_mpint(&mut self) -> Result<SecretBytes> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        let bytes = self.get_bytes_ref(len)?;
+        match bytes {
+            [] => Ok(SecretBytes::new(Vec::new())),
+            [first, ..] if first & 0x80 != 0 => {
+                Err(BufferError::InvalidData("negative mpint"))
+            }
+            [0] | [0, 0x00..=0x7f, ..] => {
+                Err(BufferError::InvalidData("non-minimal mpint"))
+            }
+            [0, rest @ ..] => Ok(SecretBytes::from_slice(rest)),
+            _ => Ok(SecretBytes::from_slice(bytes)),
+        }
+    }
+
+    /// Writes a `name-list`: comma-separated names in a length-prefixed string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidData`] if any name is empty, contains a
+    /// comma, or has characters outside printable US-ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_name_list(&["zlib", "none"])?;
+    /// assert_eq!(&buf.as_slice()[4..], b"zlib,none");
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn put_name_list<S: AsRef<str>>(&mut self, names: &[S]) -> Result<()> {
+        let mut len = names.len().saturating_sub(1);
+        for name in names {
+            let name = name.as_ref().as_bytes();
+            if !is_valid_name(name) {
+                return Err(BufferError::InvalidData("invalid name-list element"));
+            }
+            len += name.len();
+        }
+
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        self.ensure_writable("put_name_list", 4 + len)?;
+        unsafe {
+            self.put_u32_unchecked(len as u32);
+            for (i, name) in names.iter().enumerate() {
+                if i > 0 {
+                    self.put_bytes_unchecked(b",");
+                }
+                self.put_bytes_unchecked(name.as_ref().as_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a `name-list`, returning the individual names.
+    ///
+    /// A zero-length string yields an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidData`] if any element is empty (e.g.
+    /// `"a,,b"` or a trailing comma) or contains characters outside printable
+    /// US-ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(64);
+    /// buf.put_string(b"aes128-ctr,aes256-ctr")?;
+    /// buf.set_pos(0)?;
+    /// assert_eq!(buf.get_name_list()?, ["aes128-ctr", "aes256-ctr"]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn get_name_list(&mut self) -> Result<Vec<String>> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        let bytes = self.get_bytes_ref(len)?;
+        if bytes.is_empty() {
+            return Ok(Vec::new());
+        }
+        bytes
+            .split(|&b| b == b',')
+            .map(|name| {
+                if !is_valid_name(name) {
+                    return Err(BufferError::InvalidData("invalid name-list element"));
+                }
+                // Validated as ASCII above, so this cannot fail.
+                Ok(String::from_utf8_lossy(name).into_owned())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mpint_bytes(magnitude: &[u8]) -> Vec<u8> {
+        let mut buf = Buffer::new(64);
+        buf.put_mpint(magnitude).unwrap();
+        buf.as_slice().to_vec()
+    }
+
+    #[test]
+    fn test_mpint_rfc4251_vectors() {
+        assert_eq!(mpint_bytes(&[]), [0, 0, 0, 0]);
+        assert_eq!(mpint_bytes(&[0, 0]), [0, 0, 0, 0]);
+        assert_eq!(
+            mpint_bytes(&[0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7]),
+            [0, 0, 0, 8, 0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7]
+        );
+        assert_eq!(mpint_bytes(&[0x80]), [0, 0, 0, 2, 0x00, 0x80]);
+        assert_eq!(mpint_bytes(&[0x00, 0x00, 0x7f]), [0, 0, 0, 1, 0x7f]);
+    }
+
+    #[test]
+    fn test_mpint_round_trip() {
+        let mut buf = Buffer::new(64);
+        buf.put_mpint(&[0xff, 0x01]).unwrap();
+        buf.put_mpint(&[]).unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(buf.get_mpint().unwrap().expose_secret(), &[0xff, 0x01]);
+        assert!(buf.get_mpint().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mpint_rejects_malformed() {
+        for bad in [&[0x00][..], &[0x00, 0x7f], &[0x80], &[0xff, 0x80]] {
+            let mut buf = Buffer::new(64);
+            buf.put_string(bad).unwrap();
+            buf.set_pos(0).unwrap();
+            assert!(matches!(buf.get_mpint(), Err(BufferError::InvalidData(_))));
+        }
+    }
+
+    #[test]
+    fn test_name_list_round_trip() {
+        let mut buf = Buffer::new(64);
+        buf.put_name_list(&["curve25519-sha256", "ext-info-c"]).unwrap();
+        buf.put_name_list::<&str>(&[]).unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(
+            buf.get_name_list().unwrap(),
+            ["curve25519-sha256", "ext-info-c"]
+        );
+        assert!(buf.get_name_list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_name_list_rejects_malformed() {
+        let mut buf = Buffer::new(64);
+        assert!(buf.put_name_list(&["a,b"]).is_err());
+        assert!(buf.put_name_list(&[""]).is_err());
+        assert!(buf.put_name_list(&["caf\u{e9}"]).is_err());
+        assert_eq!(buf.len(), 0);
+
+        for bad in [&b"a,,b"[..], b"a,", b",a", b"a b"] {
+            let mut buf = Buffer::new(64);
+            buf.put_string(bad).unwrap();
+            buf.set_pos(0).unwrap();
+            assert!(matches!(buf.get_name_list(), Err(BufferError::InvalidData(_))));
+        }
+    }
+
+    #[test]
+    fn test_bool_and_u16() {
+        let mut buf = Buffer::new(8);
+        buf.put_bool(true).unwrap();
+        buf.put_bool(false).unwrap();
+        buf.put_u16(0xBEEF).unwrap();
+        assert_eq!(buf.as_slice(), &[1, 0, 0xBE, 0xEF]);
+
+        buf.set_pos(0).unwrap();
+        assert!(buf.get_bool().unwrap());
+        assert!(!buf.get_bool().unwrap());
+        assert_eq!(buf.get_u16().unwrap(), 0xBEEF);
+        assert!(buf.get_u8().is_err());
+    }
+}
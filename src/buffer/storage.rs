@@ -0,0 +1,202 @@
+// src/buffer/storage.rs
+//! Backing storage for [`Buffer`](super::Buffer).
+//!
+//! Ordinary buffers live in a `Vec<u8>`; locked and guarded buffers live in
+//! their own anonymous mappings.  [`Storage`] gives all of them the
+//! `Vec`-shaped interface the buffer code relies on (`len` is the writable
+//! area, `capacity` the allocation), so every `put_*`/`get_*` path works
+//! unchanged on any of them.
+
+use crate::error::Result;
+#[cfg(all(unix, feature = "std"))]
+use crate::locked::guarded::GuardedRegion;
+#[cfg(all(unix, feature = "std"))]
+use crate::locked::region::{LockedRegion, round_to_pages};
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+use zeroize::Zeroize;
+
+pub(crate) enum Storage {
+    /// Regular heap allocation.
+    Heap(Vec<u8>),
+    /// Page-aligned, `mlock`ed mapping excluded from core dumps.
+    #[cfg(all(unix, feature = "std"))]
+    Locked(LockedRegion),
+    /// Mapping between `PROT_NONE` guard pages, with a canary before the data.
+    #[cfg(all(unix, feature = "std"))]
+    Guarded(GuardedRegion),
+}
+
+impl Storage {
+    /// Allocates empty storage of the same kind with room for `capacity`
+    /// bytes.
+    pub(crate) fn with_capacity_like(&self, capacity: usize) -> Result<Self> {
+        Ok(match self {
+            Self::Heap(_) => Self::Heap(Vec::with_capacity(capacity)),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(_) => Self::Locked(LockedRegion::with_capacity(capacity)?),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(_) => Self::Guarded(GuardedRegion::with_capacity(capacity)?),
+        })
+    }
+
+    #[inline]
+    pub(crate) fn capacity(&self) -> usize {
+        match self {
+            Self::Heap(v) => v.capacity(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.capacity(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.capacity(),
+        }
+    }
+
+    /// Sets the writable length, zero-filling new bytes.  `new_len` must not
+    /// exceed [`capacity`](Self::capacity) for mapped storage.
+    #[inline]
+    pub(crate) fn resize(&mut self, new_len: usize) {
+        match self {
+            Self::Heap(v) => v.resize(new_len, 0),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.resize(new_len),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.resize(new_len),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        match self {
+            Self::Heap(v) => v.truncate(new_len),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.truncate(new_len),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.truncate(new_len),
+        }
+    }
+
+    /// Whether reallocating to fit the current length would free memory.
+    #[inline]
+    pub(crate) fn has_excess_capacity(&self) -> bool {
+        match self {
+            Self::Heap(v) => v.capacity() > v.len(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.capacity() > round_to_pages(r.as_slice().len()),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.capacity() > r.as_slice().len(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_locked(&self) -> bool {
+        #[cfg(all(unix, feature = "std"))]
+        if let Self::Locked(_) = self {
+            return true;
+        }
+        false
+    }
+
+    #[inline]
+    pub(crate) fn is_guarded(&self) -> bool {
+        #[cfg(all(unix, feature = "std"))]
+        if let Self::Guarded(_) = self {
+            return true;
+        }
+        false
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Heap(v) => v,
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.as_slice(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.as_slice(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self {
+            Self::Heap(v) => v,
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.as_mut_slice(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.as_mut_slice(),
+        }
+    }
+
+    /// Converts into a `Vec<u8>`.  Heap storage is moved; mapped storage is
+    /// copied out and the region wiped and released.
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        match self {
+            Self::Heap(v) => v,
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.as_slice().to_vec(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.as_slice().to_vec(),
+        }
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::Heap(Vec::new())
+    }
+}
+
+impl Clone for Storage {
+    /// Clones into storage of the same kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if locked or guarded storage cannot be allocated for the copy;
+    /// use [`LockedBuffer::try_clone`](crate::LockedBuffer::try_clone) to
+    /// handle that case for locked buffers.
+    fn clone(&self) -> Self {
+        match self {
+            Self::Heap(v) => Self::Heap(v.clone()),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => Self::Locked(
+                r.try_clone()
+                    .unwrap_or_else(|e| panic!("failed to clone locked storage: {}", e)),
+            ),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => Self::Guarded(
+                r.try_clone()
+                    .unwrap_or_else(|e| panic!("failed to clone guarded storage: {}", e)),
+            ),
+        }
+    }
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for Storage {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl Zeroize for Storage {
+    /// Wipes the whole allocation, including spare capacity, and sets the
+    /// length to 0.
+    fn zeroize(&mut self) {
+        match self {
+            Self::Heap(v) => v.zeroize(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Locked(r) => r.wipe(),
+            #[cfg(all(unix, feature = "std"))]
+            Self::Guarded(r) => r.wipe(),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// src/buffer/stream.rs
+//! [`StreamBuffer`]: a [`Buffer`] with independent read and write cursors.
+
+use super::core::Buffer;
+use crate::error::{BufferError, Result};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Read, Write};
+use zeroize::Zeroize;
+
+/// A streaming accumulation buffer with separate read and write cursors.
+///
+/// Bytes are appended at [`write_pos`](Self::write_pos) and consumed from
+/// [`read_pos`](Self::read_pos), so a response can be written while a request
+/// is still being parsed without any `set_pos` juggling.  The full
+/// [`Buffer`] API is reached through two short-lived guards:
+///
+/// - [`writer`](Self::writer) positions the buffer at the write cursor; the
+///   `put_*` methods append.
+/// - [`reader`](Self::reader) positions it at the read cursor; the `get_*`
+///   methods consume, and cannot read past what has been written.
+///
+/// [`compact`](Self::compact) moves the unread bytes to the front and
+/// zeroizes the vacated tail, so the same storage can be reused indefinitely.
+/// [`Read`], [`BufRead`] and [`Write`] are implemented with the same
+/// semantics.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::StreamBuffer;
+/// # use secbuf::BufferError;
+///
+/// let mut stream = StreamBuffer::new(64);
+/// stream.writer().put_string(b"request")?;
+///
+/// let mut r = stream.reader();
+/// assert_eq!(r.get_u32()?, 7);
+/// drop(r);
+///
+/// stream.writer().put_u32(0xAABB_CCDD)?; // append while unread data remains
+/// assert_eq!(stream.reader().get_bytes_ref(7)?, b"request");
+/// assert_eq!(stream.readable(), 4);
+///
+/// stream.compact();
+/// assert_eq!(stream.read_pos(), 0);
+/// assert_eq!(stream.unread(), &[0xAA, 0xBB, 0xCC, 0xDD]);
+/// # Ok::<(), BufferError>(())
+/// ```
+pub struct StreamBuffer {
+    /// Between guards, `buf.pos == buf.len` is the write cursor.
+    buf: Buffer,
+    read_pos: usize,
+}
+
+impl StreamBuffer {
+    /// Creates an empty stream buffer with `size` bytes of storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` exceeds
+    /// [`BUF_MAX_SIZE`](crate::buffer::core::BUF_MAX_SIZE).
+    pub fn new(size: usize) -> Self {
+        Self::from(Buffer::new(size))
+    }
+
+    /// Creates an empty stream buffer that grows on demand up to `max`
+    /// bytes, like [`Buffer::growable`].
+    pub fn growable(initial: usize, max: usize) -> Self {
+        Self::from(Buffer::growable(initial, max))
+    }
+
+    /// Offset of the next byte to be read.
+    #[inline]
+    pub fn read_pos(&self) -> usize {
+        self.read_pos
+    }
+
+    /// Offset at which the next byte will be written.
+    #[inline]
+    pub fn write_pos(&self) -> usize {
+        self.buf.len
+    }
+
+    /// Number of written bytes not yet read.
+    #[inline]
+    pub fn readable(&self) -> usize {
+        self.buf.len - self.read_pos
+    }
+
+    /// Bytes that can be appended without growing the storage.
+    #[inline]
+    pub fn writable(&self) -> usize {
+        self.buf.data.len() - self.buf.len
+    }
+
+    /// Current storage size.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// The written bytes not yet read.
+    #[inline]
+    pub fn unread(&self) -> &[u8] {
+        &self.buf.data[self.read_pos..self.buf.len]
+    }
+
+    /// Marks the next `n` unread bytes as consumed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if fewer than `n` bytes are
+    /// unread.
+    #[inline]
+    pub fn skip(&mut self, n: usize) -> Result<()> {
+        if n > self.readable() {
+            return Err(BufferError::overflow(
+                "skip",
+                self.read_pos,
+                n,
+                self.readable(),
+            ));
+        }
+        self.read_pos += n;
+        Ok(())
+    }
+
+    /// Returns the buffer positioned at the write cursor.
+    #[inline]
+    pub fn writer(&mut self) -> StreamWriter<'_> {
+        StreamWriter { stream: self }
+    }
+
+    /// Returns the buffer positioned at the read cursor.
+    #[inline]
+    pub fn reader(&mut self) -> StreamReader<'_> {
+        self.buf.pos = self.read_pos;
+        StreamReader { stream: self }
+    }
+
+    /// Moves the unread bytes to the front of the storage and zeroizes the
+    /// vacated tail.
+    ///
+    /// Afterwards `read_pos` is 0 and `write_pos` equals the number of
+    /// unread bytes.
+    pub fn compact(&mut self) {
+        let (start, end) = (self.read_pos, self.buf.len);
+        if start == 0 {
+            return;
+        }
+        let unread = end - start;
+        self.buf.data.copy_within(start..end, 0);
+        self.buf.data[unread..end].zeroize();
+        self.buf.len = unread;
+        self.buf.pos = unread;
+        self.read_pos = 0;
+    }
+
+    /// Zeroizes the storage and resets both cursors.
+    pub fn burn(&mut self) {
+        self.buf.burn();
+        self.read_pos = 0;
+    }
+
+    /// Returns the underlying buffer, positioned at the read cursor.
+    pub fn into_inner(mut self) -> Buffer {
+        self.buf.pos = self.read_pos;
+        self.buf
+    }
+
+    /// Restores the between-guards invariants after a guard was used.
+    #[inline]
+    fn settle(&mut self) {
+        self.read_pos = self.read_pos.min(self.buf.len);
+        self.buf.pos = self.buf.len;
+    }
+}
+
+impl From<Buffer> for StreamBuffer {
+    /// Treats `buf.pos..buf.len` as unread and appends after `buf.len`.
+    fn from(mut buf: Buffer) -> Self {
+        let read_pos = buf.pos;
+        buf.pos = buf.len;
+        Self { buf, read_pos }
+    }
+}
+
+/// The stream's [`Buffer`] positioned at the write cursor, returned by
+/// [`StreamBuffer::writer`].
+pub struct StreamWriter<'a> {
+    stream: &'a mut StreamBuffer,
+}
+
+impl core::ops::Deref for StreamWriter<'_> {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        &self.stream.buf
+    }
+}
+
+impl core::ops::DerefMut for StreamWriter<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stream.buf
+    }
+}
+
+impl Drop for StreamWriter<'_> {
+    fn drop(&mut self) {
+        self.stream.settle();
+    }
+}
+
+/// The stream's [`Buffer`] positioned at the read cursor, returned by
+/// [`StreamBuffer::reader`].
+///
+/// Reads advance the stream's read cursor; writes through a reader would
+/// overwrite unread data and should go through a
+/// [`StreamWriter`] instead.
+pub struct StreamReader<'a> {
+    stream: &'a mut StreamBuffer,
+}
+
+impl core::ops::Deref for StreamReader<'_> {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        &self.stream.buf
+    }
+}
+
+impl core::ops::DerefMut for StreamReader<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stream.buf
+    }
+}
+
+impl Drop for StreamReader<'_> {
+    fn drop(&mut self) {
+        self.stream.read_pos = self.stream.buf.pos;
+        self.stream.settle();
+    }
+}
+
+#[cfg(feature = "std")]
+impl Write for StreamBuffer {
+    #[inline]
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        self.buf.write(src)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Read for StreamBuffer {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let n = out.len().min(self.readable());
+        out[..n].copy_from_slice(&self.unread()[..n]);
+        self.read_pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "std")]
+impl BufRead for StreamBuffer {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.unread())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.read_pos = self.read_pos.saturating_add(amt).min(self.buf.len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleaved_reads_and_writes() {
+        let mut stream = StreamBuffer::new(32);
+        stream.writer().put_u16(1).unwrap();
+        assert_eq!(stream.reader().get_byte().unwrap(), 0);
+        stream.writer().put_u16(2).unwrap();
+        assert_eq!(stream.read_pos(), 1);
+        assert_eq!(stream.write_pos(), 4);
+
+        let mut r = stream.reader();
+        assert_eq!(r.get_byte().unwrap(), 1);
+        assert_eq!(r.get_u16().unwrap(), 2);
+        assert!(r.get_byte().is_err());
+        drop(r);
+        assert_eq!(stream.readable(), 0);
+    }
+
+    #[test]
+    fn test_compact_zeroizes_vacated_tail() {
+        let mut stream = StreamBuffer::new(16);
+        stream.writer().put_bytes(b"consumed|kept").unwrap();
+        stream.skip(9).unwrap();
+        stream.compact();
+
+        assert_eq!(stream.unread(), b"kept");
+        assert_eq!(stream.writable(), 12);
+        let buf = stream.into_inner();
+        assert_eq!(&buf.data[4..13], &[0u8; 9]);
+    }
+
+    #[test]
+    fn test_skip_bounds() {
+        let mut stream = StreamBuffer::new(8);
+        stream.writer().put_bytes(b"abc").unwrap();
+        assert_eq!(stream.skip(4), Err(BufferError::overflow("skip", 0, 4, 3)));
+        stream.skip(3).unwrap();
+        assert_eq!(stream.readable(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_traits() {
+        let mut stream = StreamBuffer::growable(4, 64);
+        stream.write_all(b"line one\nline two\n").unwrap();
+
+        let mut line = String::new();
+        stream.read_line(&mut line).unwrap();
+        assert_eq!(line, "line one\n");
+
+        let mut rest = [0u8; 4];
+        stream.read_exact(&mut rest).unwrap();
+        assert_eq!(&rest, b"line");
+        assert_eq!(stream.unread(), b" two\n");
+
+        stream.consume(usize::MAX);
+        assert_eq!(stream.readable(), 0);
+    }
+
+    #[test]
+    fn test_from_buffer_keeps_unread_region() {
+        let mut buf = Buffer::new(16);
+        buf.put_bytes(b"xxdata").unwrap();
+        buf.set_pos(2).unwrap();
+        let mut stream = StreamBuffer::from(buf);
+        assert_eq!(stream.unread(), b"data");
+        stream.writer().put_byte(b'!').unwrap();
+        assert_eq!(stream.into_inner().get_bytes_ref(5).unwrap(), b"data!");
+    }
+
+    #[test]
+    fn test_writer_truncation_clamps_read_pos() {
+        let mut stream = StreamBuffer::new(16);
+        stream.writer().put_bytes(b"abcdef").unwrap();
+        stream.skip(5).unwrap();
+        stream.writer().set_len(2).unwrap();
+        assert_eq!(stream.read_pos(), 2);
+        assert_eq!(stream.readable(), 0);
+    }
+}
@@ -0,0 +1,304 @@
+// src/buffer/sub_reader.rs
+//! Bounded readers over a region of a parent [`Buffer`]
+
+use super::core::Buffer;
+use super::ops::MAX_STRING_LEN;
+use crate::error::{BufferError, Result};
+
+/// A reader confined to the next `len` bytes of a parent [`Buffer`].
+///
+/// Returned by [`Buffer::sub_reader`] and [`Buffer::get_string_reader`].  The
+/// guard dereferences to the parent buffer with its valid length clamped to
+/// the end of the region, so the whole `get_*` API is available and every
+/// read past the region fails with [`BufferError::BufferOverflow`] instead
+/// of running into the data that follows.
+///
+/// When the reader drops, the parent's length is restored and its position
+/// is moved to the end of the region, whether or not every byte was read.
+/// Use [`remaining`](Buffer::remaining) to check that a structure was fully
+/// consumed.
+///
+/// The reader's own [`set_pos`](Self::set_pos), [`decr_pos`](Self::decr_pos),
+/// [`set_len`](Self::set_len) and [`incr_len`](Self::incr_len) shadow the
+/// buffer's and refuse to move outside the region, so a parser cannot seek
+/// or extend its way into the surrounding data.  Methods called on the
+/// parent directly (through `&mut *reader`) are not checked.
+///
+/// Readers nest.  They are meant for parsing: writes through a reader are
+/// not confined to the region.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::Buffer;
+/// # use secbuf::BufferError;
+///
+/// // An SSH public key blob: string(type) + string(key), wrapped in a string.
+/// let mut blob = Buffer::new(64);
+/// blob.put_string(b"ssh-ed25519")?;
+/// blob.put_string(&[0x11; 32])?;
+///
+/// let mut msg = Buffer::new(128);
+/// msg.put_string(blob.as_slice())?;
+/// msg.put_u32(7)?;
+/// msg.set_pos(0)?;
+///
+/// {
+///     let mut key = msg.get_string_reader()?;
+///     assert_eq!(key.get_string_ref()?, b"ssh-ed25519");
+///     assert_eq!(key.get_string_ref()?.len(), 32);
+///     assert!(key.get_u32().is_err()); // end of the blob, not of `msg`
+/// }
+/// assert_eq!(msg.get_u32()?, 7);
+/// # Ok::<(), BufferError>(())
+/// ```
+pub struct SubReader<'a> {
+    buf: &'a mut Buffer,
+    /// Start of the region within the parent.
+    start: usize,
+    /// End of the region within the parent.
+    end: usize,
+    /// Parent's valid length, restored on drop.
+    parent_len: usize,
+}
+
+impl SubReader<'_> {
+    /// Offset of the start of the region within the parent buffer.
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Offset of the end of the region within the parent buffer.
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Sets the position, like [`Buffer::set_pos`], within the region.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::PositionOutOfBounds`] if `pos` is before
+    /// [`start`](Self::start) or past the valid length.
+    #[inline]
+    pub fn set_pos(&mut self, pos: usize) -> Result<()> {
+        if pos < self.start {
+            return Err(BufferError::PositionOutOfBounds);
+        }
+        self.buf.set_pos(pos)
+    }
+
+    /// Moves the position back by `decr`, like [`Buffer::decr_pos`], within
+    /// the region.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::PositionOutOfBounds`] if that would move
+    /// before [`start`](Self::start).
+    #[inline]
+    pub fn decr_pos(&mut self, decr: usize) -> Result<()> {
+        if decr > self.buf.pos - self.start {
+            return Err(BufferError::PositionOutOfBounds);
+        }
+        self.buf.decr_pos(decr)
+    }
+
+    /// Sets the valid length, like [`Buffer::set_len`], within the region.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if `len` is past
+    /// [`end`](Self::end), or [`BufferError::PositionOutOfBounds`] if it is
+    /// before [`start`](Self::start).
+    pub fn set_len(&mut self, len: usize) -> Result<()> {
+        if len > self.end {
+            return Err(BufferError::overflow(
+                "set_len",
+                self.start,
+                len - self.start,
+                self.end - self.start,
+            ));
+        }
+        if len < self.start {
+            return Err(BufferError::PositionOutOfBounds);
+        }
+        self.buf.set_len(len)
+    }
+
+    /// Extends the valid length by `incr`, like [`Buffer::incr_len`],
+    /// within the region.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if the length would pass
+    /// [`end`](Self::end).
+    pub fn incr_len(&mut self, incr: usize) -> Result<()> {
+        let available = self.end.saturating_sub(self.buf.len);
+        if incr > available {
+            return Err(BufferError::overflow("incr_len", self.buf.len, incr, available));
+        }
+        self.buf.incr_len(incr)
+    }
+}
+
+impl core::ops::Deref for SubReader<'_> {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        self.buf
+    }
+}
+
+impl core::ops::DerefMut for SubReader<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.buf
+    }
+}
+
+impl Drop for SubReader<'_> {
+    fn drop(&mut self) {
+        let buf = &mut *self.buf;
+        // A write through the reader may have extended the valid data.
+        buf.len = buf.len.max(self.parent_len);
+        buf.pos = self.end.min(buf.len);
+    }
+}
+
+impl Buffer {
+    /// Returns a reader limited to the next `len` bytes.
+    ///
+    /// See [`SubReader`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if fewer than `len` bytes
+    /// remain.
+    pub fn sub_reader(&mut self, len: usize) -> Result<SubReader<'_>> {
+        self.ensure_readable("sub_reader", len)?;
+        let start = self.pos;
+        let end = start + len;
+        let parent_len = self.len;
+        self.len = end;
+        Ok(SubReader {
+            buf: self,
+            start,
+            end,
+            parent_len,
+        })
+    }
+
+    /// Reads an SSH-style string length and returns a reader limited to the
+    /// string's payload, without copying it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`get_string_ref`](Self::get_string_ref).
+    pub fn get_string_reader(&mut self) -> Result<SubReader<'_>> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        self.sub_reader(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Buffer {
+        let mut buf = Buffer::new(64);
+        buf.put_u32(1).unwrap();
+        buf.put_u32(2).unwrap();
+        buf.put_u32(3).unwrap();
+        buf.set_pos(0).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_reads_stop_at_region_end() {
+        let mut buf = sample();
+        {
+            let mut sub = buf.sub_reader(6).unwrap();
+            assert_eq!(sub.get_u32().unwrap(), 1);
+            assert_eq!(sub.remaining(), 2);
+            assert_eq!(sub.get_u32(), Err(BufferError::overflow("get_u32", 4, 4, 2)));
+            assert_eq!(sub.get_u16().unwrap(), 0);
+        }
+        assert_eq!(buf.len(), 12);
+        assert_eq!(buf.pos(), 6);
+    }
+
+    #[test]
+    fn test_unread_remainder_is_skipped() {
+        let mut buf = sample();
+        drop(buf.sub_reader(8).unwrap());
+        assert_eq!(buf.get_u32().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_region_must_fit() {
+        let mut buf = sample();
+        buf.set_pos(4).unwrap();
+        assert!(buf.sub_reader(9).is_err());
+        assert!(buf.sub_reader(usize::MAX).is_err());
+        assert_eq!(buf.len(), 12);
+        assert_eq!(buf.pos(), 4);
+    }
+
+    #[test]
+    fn test_nested_readers() {
+        let mut buf = sample();
+        {
+            let mut outer = buf.sub_reader(8).unwrap();
+            {
+                let mut inner = outer.sub_reader(4).unwrap();
+                assert_eq!(inner.get_u32().unwrap(), 1);
+                assert!(inner.get_byte().is_err());
+            }
+            assert_eq!(outer.len(), 8);
+            assert_eq!(outer.get_u32().unwrap(), 2);
+            assert!(outer.get_byte().is_err());
+        }
+        assert_eq!(buf.get_u32().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_cannot_seek_or_extend_out_of_region() {
+        let mut buf = sample();
+        buf.set_pos(4).unwrap();
+        {
+            let mut sub = buf.sub_reader(4).unwrap();
+            assert_eq!(sub.set_pos(0), Err(BufferError::PositionOutOfBounds));
+            assert_eq!(sub.decr_pos(1), Err(BufferError::PositionOutOfBounds));
+            assert_eq!(sub.set_len(12), Err(BufferError::overflow("set_len", 4, 8, 4)));
+            assert_eq!(sub.set_len(2), Err(BufferError::PositionOutOfBounds));
+            assert_eq!(sub.incr_len(1), Err(BufferError::overflow("incr_len", 8, 1, 0)));
+            assert_eq!(sub.get_u32().unwrap(), 2);
+            assert!(sub.get_byte().is_err());
+
+            // Moving within the region still works.
+            sub.decr_pos(4).unwrap();
+            sub.set_len(6).unwrap();
+            assert_eq!(sub.get_u16().unwrap(), 0);
+            assert!(sub.get_byte().is_err());
+        }
+        assert_eq!(buf.len(), 12);
+        assert_eq!(buf.get_u32().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_get_string_reader() {
+        let mut buf = Buffer::new(32);
+        buf.put_string(&[0, 0, 0, 9, 0xAB]).unwrap();
+        buf.put_byte(0xCD).unwrap();
+        buf.set_pos(0).unwrap();
+        {
+            let mut sub = buf.get_string_reader().unwrap();
+            assert_eq!(sub.get_u32().unwrap(), 9);
+            assert_eq!(sub.get_byte().unwrap(), 0xAB);
+            assert!(sub.get_byte().is_err());
+        }
+        assert_eq!(buf.get_byte().unwrap(), 0xCD);
+    }
+}
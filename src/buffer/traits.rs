@@ -0,0 +1,744 @@
+// src/buffer/traits.rs
+//! [`BufferRead`] and [`BufferWrite`]: the wire-format API shared by every
+//! buffer type
+
+use super::array::ArrayBuffer;
+use super::core::Buffer;
+use super::ops::MAX_STRING_LEN;
+use crate::circular::CircularBuffer;
+use crate::error::{BufferError, Result};
+use crate::secret::SecretBytes;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Sequential reads of big-endian integers, raw bytes and SSH-style strings.
+///
+/// Implementors provide [`readable`](Self::readable),
+/// [`read_into`](Self::read_into) and [`skip`](Self::skip); everything else
+/// has a default built on those.  Overflow errors raised by the defaults
+/// report an `offset` of 0, i.e. relative to the current read position;
+/// [`Buffer`] and [`ArrayBuffer`] override every method and report absolute
+/// offsets.
+///
+/// Implemented for [`Buffer`], [`ArrayBuffer`], the pooled wrappers,
+/// [`CircularBuffer`] (reads consume from the ring) and `&[u8]` (reads
+/// shrink the slice).
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::{ArrayBuffer, Buffer, BufferRead, Result};
+///
+/// fn parse_header<R: BufferRead>(r: &mut R) -> Result<(u32, u8)> {
+///     Ok((r.get_u32()?, r.get_u8()?))
+/// }
+///
+/// let mut heap = Buffer::from_vec(vec![0, 0, 0, 12, 5]);
+/// let mut stack = ArrayBuffer::<16>::new();
+/// stack.put_bytes(&[0, 0, 0, 12, 5])?;
+/// stack.set_pos(0)?;
+/// assert_eq!(parse_header(&mut heap)?, parse_header(&mut stack)?);
+/// # Ok::<(), secbuf::BufferError>(())
+/// ```
+pub trait BufferRead {
+    /// Number of bytes left to read.
+    fn readable(&self) -> usize;
+
+    /// Fills `dst` with the next `dst.len()` bytes and advances past them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if fewer than `dst.len()`
+    /// bytes remain, without consuming anything.
+    fn read_into(&mut self, dst: &mut [u8]) -> Result<()>;
+
+    /// Advances past the next `n` bytes without returning them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if fewer than `n` bytes
+    /// remain, without consuming anything.
+    fn skip(&mut self, n: usize) -> Result<()>;
+
+    /// Reads a single byte.
+    #[inline]
+    fn get_u8(&mut self) -> Result<u8> {
+        Ok(read_array::<_, 1>(self, "get_u8")?[0])
+    }
+
+    /// Reads a single byte. Alias of [`get_u8`](Self::get_u8).
+    #[inline]
+    fn get_byte(&mut self) -> Result<u8> {
+        self.get_u8()
+    }
+
+    /// Reads a boolean (0 = false, non-zero = true).
+    #[inline]
+    fn get_bool(&mut self) -> Result<bool> {
+        Ok(self.get_u8()? != 0)
+    }
+
+    /// Reads a big-endian `u16`.
+    #[inline]
+    fn get_u16(&mut self) -> Result<u16> {
+        read_array(self, "get_u16").map(u16::from_be_bytes)
+    }
+
+    /// Reads a big-endian `u32`.
+    #[inline]
+    fn get_u32(&mut self) -> Result<u32> {
+        read_array(self, "get_u32").map(u32::from_be_bytes)
+    }
+
+    /// Reads a big-endian `u64`.
+    #[inline]
+    fn get_u64(&mut self) -> Result<u64> {
+        read_array(self, "get_u64").map(u64::from_be_bytes)
+    }
+
+    /// Reads `len` bytes into an owned `Vec`, which is **not** zeroized on
+    /// drop.
+    fn get_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+        // Check before allocating so a bogus length costs nothing.
+        check_readable(self, "get_bytes", len)?;
+        let mut out = vec![0; len];
+        self.read_into(&mut out)?;
+        Ok(out)
+    }
+
+    /// Reads `len` bytes into a [`SecretBytes`] that is zeroized on drop.
+    fn get_bytes_secret(&mut self, len: usize) -> Result<SecretBytes> {
+        self.get_bytes(len).map(SecretBytes::new)
+    }
+
+    /// Reads an SSH-style string (4-byte length prefix + data).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if the length exceeds the SSH
+    /// string limit (400 000 bytes).
+    fn get_string(&mut self) -> Result<Vec<u8>> {
+        let len = get_string_len(self)?;
+        self.get_bytes(len)
+    }
+
+    /// Reads an SSH-style string into a [`SecretBytes`] that is zeroized on
+    /// drop.
+    fn get_string_secret(&mut self) -> Result<SecretBytes> {
+        self.get_string().map(SecretBytes::new)
+    }
+
+    /// Skips over an SSH-style string without reading the data.
+    fn eat_string(&mut self) -> Result<()> {
+        let len = get_string_len(self)?;
+        self.skip(len)
+    }
+}
+
+/// Sequential writes of big-endian integers, raw bytes and SSH-style
+/// strings.
+///
+/// Implementors provide [`writable`](Self::writable) and
+/// [`put_bytes`](Self::put_bytes); everything else has a default built on
+/// those.  As with [`BufferRead`], overflow errors raised by the defaults
+/// report offsets relative to the current write position.
+///
+/// Implemented for [`Buffer`], [`ArrayBuffer`], the pooled wrappers,
+/// [`CircularBuffer`] (writes append to the ring) and `&mut [u8]` (writes
+/// fill the slice from the front and shrink it).
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::{ArrayBuffer, Buffer, BufferWrite, Result};
+///
+/// fn write_service_request<W: BufferWrite>(w: &mut W) -> Result<()> {
+///     w.put_u8(5)?; // SSH_MSG_SERVICE_REQUEST
+///     w.put_string(b"ssh-userauth")
+/// }
+///
+/// let mut heap = Buffer::new(64);
+/// let mut stack = ArrayBuffer::<64>::new();
+/// write_service_request(&mut heap)?;
+/// write_service_request(&mut stack)?;
+/// assert_eq!(heap.as_slice(), stack.as_slice());
+/// # Ok::<(), secbuf::BufferError>(())
+/// ```
+pub trait BufferWrite {
+    /// Number of bytes that can still be written, counting any growth the
+    /// buffer allows.
+    fn writable(&self) -> usize;
+
+    /// Writes `src` and advances past it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::BufferOverflow`] if fewer than `src.len()`
+    /// bytes can be written, without writing anything.
+    fn put_bytes(&mut self, src: &[u8]) -> Result<()>;
+
+    /// Writes a single byte.
+    #[inline]
+    fn put_u8(&mut self, val: u8) -> Result<()> {
+        write_checked(self, "put_u8", &[val])
+    }
+
+    /// Writes a single byte. Alias of [`put_u8`](Self::put_u8).
+    #[inline]
+    fn put_byte(&mut self, val: u8) -> Result<()> {
+        self.put_u8(val)
+    }
+
+    /// Writes an SSH `boolean`: `1` for true, `0` for false.
+    #[inline]
+    fn put_bool(&mut self, val: bool) -> Result<()> {
+        self.put_u8(val as u8)
+    }
+
+    /// Writes a big-endian `u16`.
+    #[inline]
+    fn put_u16(&mut self, val: u16) -> Result<()> {
+        write_checked(self, "put_u16", &val.to_be_bytes())
+    }
+
+    /// Writes a big-endian `u32`.
+    #[inline]
+    fn put_u32(&mut self, val: u32) -> Result<()> {
+        write_checked(self, "put_u32", &val.to_be_bytes())
+    }
+
+    /// Writes a big-endian `u64`.
+    #[inline]
+    fn put_u64(&mut self, val: u64) -> Result<()> {
+        write_checked(self, "put_u64", &val.to_be_bytes())
+    }
+
+    /// Writes an SSH-style string (4-byte length prefix + data).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidString`] if `s` exceeds the SSH string
+    /// limit (400 000 bytes).
+    fn put_string(&mut self, s: &[u8]) -> Result<()> {
+        if s.len() > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        // Check the whole string up front so a failure writes nothing.
+        let needed = 4 + s.len();
+        if needed > self.writable() {
+            return Err(BufferError::overflow("put_string", 0, needed, self.writable()));
+        }
+        self.put_bytes(&(s.len() as u32).to_be_bytes())?;
+        self.put_bytes(s)
+    }
+}
+
+#[inline]
+fn check_readable<R: BufferRead + ?Sized>(r: &R, op: &'static str, n: usize) -> Result<()> {
+    if n > r.readable() {
+        return Err(BufferError::overflow(op, 0, n, r.readable()));
+    }
+    Ok(())
+}
+
+#[inline]
+fn read_array<R: BufferRead + ?Sized, const N: usize>(
+    r: &mut R,
+    op: &'static str,
+) -> Result<[u8; N]> {
+    check_readable(r, op, N)?;
+    let mut out = [0; N];
+    r.read_into(&mut out)?;
+    Ok(out)
+}
+
+#[inline]
+fn get_string_len<R: BufferRead + ?Sized>(r: &mut R) -> Result<usize> {
+    let len = r.get_u32()? as usize;
+    if len > MAX_STRING_LEN {
+        return Err(BufferError::InvalidString);
+    }
+    Ok(len)
+}
+
+#[inline]
+fn write_checked<W: BufferWrite + ?Sized>(w: &mut W, op: &'static str, src: &[u8]) -> Result<()> {
+    if src.len() > w.writable() {
+        return Err(BufferError::overflow(op, 0, src.len(), w.writable()));
+    }
+    w.put_bytes(src)
+}
+
+/// Implements both traits by forwarding every method to the inherent method
+/// of the same name, so the concrete type keeps its own error context and
+/// fast paths.
+macro_rules! impl_by_inherent {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> BufferRead for $ty {
+            #[inline]
+            fn readable(&self) -> usize {
+                self.remaining()
+            }
+
+            #[inline]
+            fn read_into(&mut self, dst: &mut [u8]) -> Result<()> {
+                self.ensure_readable("read_into", dst.len())?;
+                dst.copy_from_slice(<$ty>::get_bytes_ref(self, dst.len())?);
+                Ok(())
+            }
+
+            #[inline]
+            fn skip(&mut self, n: usize) -> Result<()> {
+                self.ensure_readable("skip", n)?;
+                <$ty>::incr_pos(self, n)
+            }
+
+            #[inline]
+            fn get_u8(&mut self) -> Result<u8> {
+                <$ty>::get_u8(self)
+            }
+
+            #[inline]
+            fn get_byte(&mut self) -> Result<u8> {
+                <$ty>::get_byte(self)
+            }
+
+            #[inline]
+            fn get_bool(&mut self) -> Result<bool> {
+                <$ty>::get_bool(self)
+            }
+
+            #[inline]
+            fn get_u16(&mut self) -> Result<u16> {
+                <$ty>::get_u16(self)
+            }
+
+            #[inline]
+            fn get_u32(&mut self) -> Result<u32> {
+                <$ty>::get_u32(self)
+            }
+
+            #[inline]
+            fn get_u64(&mut self) -> Result<u64> {
+                <$ty>::get_u64(self)
+            }
+
+            #[inline]
+            fn get_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+                <$ty>::get_bytes(self, len)
+            }
+
+            #[inline]
+            fn get_bytes_secret(&mut self, len: usize) -> Result<SecretBytes> {
+                <$ty>::get_bytes_secret(self, len)
+            }
+
+            #[inline]
+            fn get_string(&mut self) -> Result<Vec<u8>> {
+                <$ty>::get_string(self)
+            }
+
+            #[inline]
+            fn get_string_secret(&mut self) -> Result<SecretBytes> {
+                <$ty>::get_string_secret(self)
+            }
+
+            #[inline]
+            fn eat_string(&mut self) -> Result<()> {
+                <$ty>::eat_string(self)
+            }
+        }
+
+        impl<$($generics)*> BufferWrite for $ty {
+            #[inline]
+            fn writable(&self) -> usize {
+                self.writable_room()
+            }
+
+            #[inline]
+            fn put_bytes(&mut self, src: &[u8]) -> Result<()> {
+                <$ty>::put_bytes(self, src)
+            }
+
+            #[inline]
+            fn put_u8(&mut self, val: u8) -> Result<()> {
+                <$ty>::put_u8(self, val)
+            }
+
+            #[inline]
+            fn put_byte(&mut self, val: u8) -> Result<()> {
+                <$ty>::put_byte(self, val)
+            }
+
+            #[inline]
+            fn put_bool(&mut self, val: bool) -> Result<()> {
+                <$ty>::put_bool(self, val)
+            }
+
+            #[inline]
+            fn put_u16(&mut self, val: u16) -> Result<()> {
+                <$ty>::put_u16(self, val)
+            }
+
+            #[inline]
+            fn put_u32(&mut self, val: u32) -> Result<()> {
+                <$ty>::put_u32(self, val)
+            }
+
+            #[inline]
+            fn put_u64(&mut self, val: u64) -> Result<()> {
+                <$ty>::put_u64(self, val)
+            }
+
+            #[inline]
+            fn put_string(&mut self, s: &[u8]) -> Result<()> {
+                <$ty>::put_string(self, s)
+            }
+        }
+    };
+}
+
+impl_by_inherent!([] Buffer);
+impl_by_inherent!([const N: usize] ArrayBuffer<N>);
+
+/// Implements both traits for a wrapper that dereferences to a [`Buffer`].
+#[cfg(feature = "std")]
+macro_rules! impl_by_deref {
+    ($ty:ty) => {
+        impl BufferRead for $ty {
+            #[inline]
+            fn readable(&self) -> usize {
+                BufferRead::readable(&**self)
+            }
+
+            #[inline]
+            fn read_into(&mut self, dst: &mut [u8]) -> Result<()> {
+                BufferRead::read_into(&mut **self, dst)
+            }
+
+            #[inline]
+            fn skip(&mut self, n: usize) -> Result<()> {
+                BufferRead::skip(&mut **self, n)
+            }
+
+            #[inline]
+            fn get_u8(&mut self) -> Result<u8> {
+                Buffer::get_u8(self)
+            }
+
+            #[inline]
+            fn get_byte(&mut self) -> Result<u8> {
+                Buffer::get_byte(self)
+            }
+
+            #[inline]
+            fn get_bool(&mut self) -> Result<bool> {
+                Buffer::get_bool(self)
+            }
+
+            #[inline]
+            fn get_u16(&mut self) -> Result<u16> {
+                Buffer::get_u16(self)
+            }
+
+            #[inline]
+            fn get_u32(&mut self) -> Result<u32> {
+                Buffer::get_u32(self)
+            }
+
+            #[inline]
+            fn get_u64(&mut self) -> Result<u64> {
+                Buffer::get_u64(self)
+            }
+
+            #[inline]
+            fn get_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+                Buffer::get_bytes(self, len)
+            }
+
+            #[inline]
+            fn get_bytes_secret(&mut self, len: usize) -> Result<SecretBytes> {
+                Buffer::get_bytes_secret(self, len)
+            }
+
+            #[inline]
+            fn get_string(&mut self) -> Result<Vec<u8>> {
+                Buffer::get_string(self)
+            }
+
+            #[inline]
+            fn get_string_secret(&mut self) -> Result<SecretBytes> {
+                Buffer::get_string_secret(self)
+            }
+
+            #[inline]
+            fn eat_string(&mut self) -> Result<()> {
+                Buffer::eat_string(self)
+            }
+        }
+
+        impl BufferWrite for $ty {
+            #[inline]
+            fn writable(&self) -> usize {
+                BufferWrite::writable(&**self)
+            }
+
+            #[inline]
+            fn put_bytes(&mut self, src: &[u8]) -> Result<()> {
+                Buffer::put_bytes(self, src)
+            }
+
+            #[inline]
+            fn put_u8(&mut self, val: u8) -> Result<()> {
+                Buffer::put_u8(self, val)
+            }
+
+            #[inline]
+            fn put_byte(&mut self, val: u8) -> Result<()> {
+                Buffer::put_byte(self, val)
+            }
+
+            #[inline]
+            fn put_bool(&mut self, val: bool) -> Result<()> {
+                Buffer::put_bool(self, val)
+            }
+
+            #[inline]
+            fn put_u16(&mut self, val: u16) -> Result<()> {
+                Buffer::put_u16(self, val)
+            }
+
+            #[inline]
+            fn put_u32(&mut self, val: u32) -> Result<()> {
+                Buffer::put_u32(self, val)
+            }
+
+            #[inline]
+            fn put_u64(&mut self, val: u64) -> Result<()> {
+                Buffer::put_u64(self, val)
+            }
+
+            #[inline]
+            fn put_string(&mut self, s: &[u8]) -> Result<()> {
+                Buffer::put_string(self, s)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_by_deref!(crate::pool::PooledBuffer);
+#[cfg(feature = "std")]
+impl_by_deref!(crate::pool::FastPooledBuffer);
+
+impl BufferRead for CircularBuffer {
+    #[inline]
+    fn readable(&self) -> usize {
+        self.used()
+    }
+
+    fn read_into(&mut self, dst: &mut [u8]) -> Result<()> {
+        check_readable(self, "read_into", dst.len())?;
+        self.read(dst)?;
+        Ok(())
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        check_readable(self, "skip", n)?;
+        self.incr_read(n)
+    }
+}
+
+impl BufferWrite for CircularBuffer {
+    #[inline]
+    fn writable(&self) -> usize {
+        self.available()
+    }
+
+    /// Writes `src`, wrapping around the end of the ring as needed.
+    ///
+    /// Unlike [`CircularBuffer::write`], a short ring reports
+    /// [`BufferError::BufferOverflow`] rather than
+    /// [`BufferError::InsufficientSpace`], like every other implementor.
+    fn put_bytes(&mut self, src: &[u8]) -> Result<()> {
+        if src.len() > self.available() {
+            return Err(BufferError::overflow("put_bytes", 0, src.len(), self.available()));
+        }
+        self.write(src)?;
+        Ok(())
+    }
+}
+
+/// Reads from the front of the slice, shrinking it past the consumed bytes
+/// like `std::io::Read` does.
+impl BufferRead for &[u8] {
+    #[inline]
+    fn readable(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn read_into(&mut self, dst: &mut [u8]) -> Result<()> {
+        check_readable(self, "read_into", dst.len())?;
+        let (head, tail) = self.split_at(dst.len());
+        dst.copy_from_slice(head);
+        *self = tail;
+        Ok(())
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<()> {
+        check_readable(self, "skip", n)?;
+        *self = &self[n..];
+        Ok(())
+    }
+}
+
+/// Writes to the front of the slice, shrinking it past the written bytes
+/// like `std::io::Write` does.
+impl BufferWrite for &mut [u8] {
+    #[inline]
+    fn writable(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn put_bytes(&mut self, src: &[u8]) -> Result<()> {
+        if src.len() > self.len() {
+            return Err(BufferError::overflow("put_bytes", 0, src.len(), self.len()));
+        }
+        let (head, tail) = core::mem::take(self).split_at_mut(src.len());
+        head.copy_from_slice(src);
+        *self = tail;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a small KEXINIT-like message through the traits only.
+    fn encode<W: BufferWrite>(w: &mut W) -> Result<()> {
+        w.put_u8(20)?;
+        w.put_bool(false)?;
+        w.put_u16(0x0102)?;
+        w.put_u32(0x0304_0506)?;
+        w.put_u64(u64::MAX)?;
+        w.put_string(b"curve25519-sha256")?;
+        w.put_bytes(&[0xAA; 3])
+    }
+
+    fn decode<R: BufferRead>(r: &mut R) -> Result<()> {
+        assert_eq!(r.get_u8()?, 20);
+        assert!(!r.get_bool()?);
+        assert_eq!(r.get_u16()?, 0x0102);
+        assert_eq!(r.get_u32()?, 0x0304_0506);
+        assert_eq!(r.get_u64()?, u64::MAX);
+        assert_eq!(r.get_string()?, b"curve25519-sha256");
+        r.skip(1)?;
+        assert_eq!(r.get_bytes_secret(2)?.expose_secret(), &[0xAA; 2]);
+        assert_eq!(r.readable(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_buffer_and_array_agree() {
+        let mut buf = Buffer::new(64);
+        let mut arr = ArrayBuffer::<64>::new();
+        encode(&mut buf).unwrap();
+        encode(&mut arr).unwrap();
+        assert_eq!(buf.as_slice(), arr.as_slice());
+
+        buf.set_pos(0).unwrap();
+        arr.set_pos(0).unwrap();
+        decode(&mut buf).unwrap();
+        decode(&mut arr).unwrap();
+    }
+
+    #[test]
+    fn test_overrides_keep_absolute_offsets() {
+        let mut arr = ArrayBuffer::<8>::new();
+        arr.put_u32(1).unwrap();
+        assert_eq!(
+            BufferWrite::put_u64(&mut arr, 2),
+            Err(BufferError::overflow("put_u64", 4, 8, 4))
+        );
+        assert_eq!(BufferWrite::writable(&arr), 4);
+        assert_eq!(
+            BufferRead::skip(&mut arr, 1),
+            Err(BufferError::overflow("skip", 4, 1, 0))
+        );
+    }
+
+    #[test]
+    fn test_growable_buffer_counts_growth_as_writable() {
+        let buf = Buffer::growable(8, 256);
+        assert_eq!(BufferWrite::writable(&buf), 256);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pooled_buffers() {
+        use crate::pool::{BufferPool, FastBufferPool, PoolConfig};
+
+        let config = PoolConfig {
+            buffer_size: 64,
+            max_pool_size: 2,
+            min_pool_size: 0,
+            ..PoolConfig::default()
+        };
+        let pool = BufferPool::new(config.clone());
+        let fast = FastBufferPool::new(config);
+
+        let mut a = pool.acquire();
+        let mut b = fast.acquire();
+        encode(&mut a).unwrap();
+        encode(&mut b).unwrap();
+        assert_eq!(a.as_slice(), b.as_slice());
+
+        a.set_pos(0).unwrap();
+        b.set_pos(0).unwrap();
+        decode(&mut a).unwrap();
+        decode(&mut b).unwrap();
+    }
+
+    #[test]
+    fn test_circular_buffer_wraps() {
+        let mut ring = CircularBuffer::new_pow2(6);
+        ring.put_bytes(&[0; 40]).unwrap();
+        ring.skip(40).unwrap();
+
+        // The message straddles the end of the ring.
+        encode(&mut ring).unwrap();
+        decode(&mut ring).unwrap();
+        assert!(ring.is_empty());
+
+        assert_eq!(
+            BufferWrite::put_bytes(&mut ring, &[0; 65]),
+            Err(BufferError::overflow("put_bytes", 0, 65, 64))
+        );
+        assert_eq!(ring.get_u8(), Err(BufferError::overflow("get_u8", 0, 1, 0)));
+    }
+
+    #[test]
+    fn test_slices() {
+        let mut storage = [0u8; 64];
+        let mut out: &mut [u8] = &mut storage;
+        encode(&mut out).unwrap();
+        let written = 64 - out.len();
+        assert_eq!(
+            out.put_string(&[0; 61]),
+            Err(BufferError::overflow("put_string", 0, 65, 64 - written))
+        );
+
+        let mut buf = Buffer::new(64);
+        encode(&mut buf).unwrap();
+        assert_eq!(&storage[..written], buf.as_slice());
+
+        let mut input: &[u8] = &storage[..written];
+        decode(&mut input).unwrap();
+        assert!(input.is_empty());
+        assert!(input.get_u32().is_err());
+    }
+}
@@ -4,6 +4,38 @@
 use super::core::Buffer;
 
 impl Buffer {
+    /// Writes a `u16` in big-endian format without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// Caller MUST guarantee: `self.pos + 2 <= self.capacity()`.
+    #[inline(always)]
+    pub unsafe fn put_u16_unchecked(&mut self, val: u16) {
+        debug_assert!(self.pos + 2 <= self.capacity());
+
+        let ptr = unsafe { self.data.as_mut_ptr().add(self.pos) };
+        unsafe { ptr.cast::<u16>().write_unaligned(val.to_be()) };
+        self.pos += 2;
+        if self.pos > self.len {
+            self.len = self.pos;
+        }
+    }
+
+    /// Reads a `u16` in big-endian format without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// Caller MUST guarantee: `self.pos + 2 <= self.len`.
+    #[inline(always)]
+    pub unsafe fn get_u16_unchecked(&mut self) -> u16 {
+        debug_assert!(self.pos + 2 <= self.len);
+
+        let ptr = unsafe { self.data.as_ptr().add(self.pos) };
+        let val = unsafe { ptr.cast::<u16>().read_unaligned() };
+        self.pos += 2;
+        u16::from_be(val)
+    }
+
     /// Writes a `u32` in big-endian format without bounds checking.
     ///
     /// # Safety
@@ -88,7 +120,7 @@ impl Buffer {
         debug_assert!(self.pos + bytes.len() <= self.capacity());
 
         let ptr = unsafe { self.data.as_mut_ptr().add(self.pos) };
-        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
+        unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
         self.pos += bytes.len();
         if self.pos > self.len {
             self.len = self.pos;
@@ -105,7 +137,7 @@ impl Buffer {
         debug_assert!(self.pos + len <= self.len);
 
         let ptr = unsafe { self.data.as_ptr().add(self.pos) };
-        let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, len) };
         self.pos += len;
         slice
     }
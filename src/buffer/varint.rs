@@ -0,0 +1,303 @@
+// src/buffer/varint.rs
+//! Variable-length integer codecs: unsigned LEB128, zigzag and QUIC varints.
+//!
+//! Writers always emit the shortest encoding.  Readers reject anything that
+//! does not fit in 64 bits ([`BufferError::VarintOverflow`]) and, for
+//! LEB128, any encoding with redundant trailing continuation groups
+//! ([`BufferError::VarintNonCanonical`]), so a given value has exactly one
+//! accepted wire form.
+
+use super::core::Buffer;
+use crate::error::{BufferError, Result};
+
+/// Longest valid LEB128 encoding of a `u64` (⌈64 / 7⌉ bytes).
+const MAX_LEB128_LEN: usize = 10;
+
+/// Largest value representable as a QUIC varint (RFC 9000 §16).
+pub const QUIC_VARINT_MAX: u64 = (1 << 62) - 1;
+
+/// Number of bytes needed to LEB128-encode `val`.
+#[inline]
+fn uleb128_len(val: u64) -> usize {
+    let bits = 64 - (val | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Number of bytes needed to QUIC-encode `val`, or `None` if out of range.
+#[inline]
+fn quic_varint_len(val: u64) -> Option<usize> {
+    match val {
+        0..=0x3f => Some(1),
+        0x40..=0x3fff => Some(2),
+        0x4000..=0x3fff_ffff => Some(4),
+        0x4000_0000..=QUIC_VARINT_MAX => Some(8),
+        _ => None,
+    }
+}
+
+impl Buffer {
+    /// Writes an unsigned LEB128 varint using the shortest encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(16);
+    /// buf.put_uleb128(624_485)?;
+    /// assert_eq!(buf.as_slice(), &[0xe5, 0x8e, 0x26]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn put_uleb128(&mut self, mut val: u64) -> Result<()> {
+        let len = uleb128_len(val);
+        self.ensure_writable("put_uleb128", len)?;
+        for i in 0..len {
+            let more = if i + 1 < len { 0x80 } else { 0 };
+            self.data[self.pos + i] = (val & 0x7f) as u8 | more;
+            val >>= 7;
+        }
+        self.pos += len;
+        if self.pos > self.len {
+            self.len = self.pos;
+        }
+        Ok(())
+    }
+
+    /// Reads an unsigned LEB128 varint.
+    ///
+    /// # Errors
+    ///
+    /// - [`BufferError::BufferOverflow`] if the data ends mid-varint.
+    /// - [`BufferError::VarintOverflow`] if the value does not fit in a `u64`.
+    /// - [`BufferError::VarintNonCanonical`] if the encoding is over-long
+    ///   (e.g. `[0x80, 0x00]` for zero).
+    pub fn get_uleb128(&mut self) -> Result<u64> {
+        let avail = &self.data[self.pos..self.len];
+        let mut val = 0u64;
+        for (i, &byte) in avail.iter().take(MAX_LEB128_LEN).enumerate() {
+            let group = (byte & 0x7f) as u64;
+            // The tenth byte may only contribute the single remaining bit.
+            if i == MAX_LEB128_LEN - 1 && byte > 0x01 {
+                return Err(BufferError::VarintOverflow);
+            }
+            val |= group << (7 * i);
+            if byte & 0x80 == 0 {
+                if i > 0 && byte == 0 {
+                    return Err(BufferError::VarintNonCanonical);
+                }
+                self.pos += i + 1;
+                return Ok(val);
+            }
+        }
+        if avail.len() >= MAX_LEB128_LEN {
+            Err(BufferError::VarintOverflow)
+        } else {
+            Err(BufferError::overflow(
+                "get_uleb128",
+                self.pos,
+                avail.len() + 1,
+                avail.len(),
+            ))
+        }
+    }
+
+    /// Writes a signed integer as a zigzag-encoded LEB128 varint.
+    ///
+    /// Zigzag maps small-magnitude negatives to small unsigned values
+    /// (`0, -1, 1, -2, …` → `0, 1, 2, 3, …`) so they stay short on the wire.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(16);
+    /// buf.put_zigzag_leb128(-1)?;
+    /// assert_eq!(buf.as_slice(), &[0x01]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    #[inline]
+    pub fn put_zigzag_leb128(&mut self, val: i64) -> Result<()> {
+        self.put_uleb128(((val << 1) ^ (val >> 63)) as u64)
+    }
+
+    /// Reads a zigzag-encoded LEB128 varint as a signed integer.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`get_uleb128`](Self::get_uleb128).
+    #[inline]
+    pub fn get_zigzag_leb128(&mut self) -> Result<i64> {
+        let raw = self.get_uleb128()?;
+        Ok((raw >> 1) as i64 ^ -((raw & 1) as i64))
+    }
+
+    /// Writes a QUIC variable-length integer (RFC 9000 §16) using the
+    /// shortest of the 1/2/4/8-byte forms.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::VarintOutOfRange`] if `val` exceeds
+    /// [`QUIC_VARINT_MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::Buffer;
+    /// # use secbuf::BufferError;
+    ///
+    /// let mut buf = Buffer::new(16);
+    /// buf.put_quic_varint(15_293)?;
+    /// assert_eq!(buf.as_slice(), &[0x7b, 0xbd]);
+    /// # Ok::<(), BufferError>(())
+    /// ```
+    pub fn put_quic_varint(&mut self, val: u64) -> Result<()> {
+        let len = quic_varint_len(val).ok_or(BufferError::VarintOutOfRange)?;
+        self.ensure_writable("put_quic_varint", len)?;
+        // The two high bits carry log2(len).
+        let tag = (len.trailing_zeros() as u64) << (len * 8 - 2);
+        let bytes = (val | tag).to_be_bytes();
+        unsafe {
+            self.put_bytes_unchecked(&bytes[8 - len..]);
+        }
+        Ok(())
+    }
+
+    /// Reads a QUIC variable-length integer.
+    ///
+    /// RFC 9000 permits senders to use a longer form than necessary, so this
+    /// accepts any of the four lengths.  Use
+    /// [`get_quic_varint_canonical`](Self::get_quic_varint_canonical) where
+    /// the minimal form is mandatory (e.g. frame types).
+    pub fn get_quic_varint(&mut self) -> Result<u64> {
+        self.ensure_readable("get_quic_varint", 1)?;
+        let len = 1usize << (self.data[self.pos] >> 6);
+        self.ensure_readable("get_quic_varint", len)?;
+        let mut bytes = [0u8; 8];
+        bytes[8 - len..].copy_from_slice(unsafe { self.get_bytes_unchecked(len) });
+        bytes[8 - len] &= 0x3f;
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    /// Reads a QUIC variable-length integer, rejecting over-long encodings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::VarintNonCanonical`] if a shorter form could
+    /// have represented the value.
+    pub fn get_quic_varint_canonical(&mut self) -> Result<u64> {
+        let start = self.pos;
+        let val = self.get_quic_varint()?;
+        if quic_varint_len(val) != Some(self.pos - start) {
+            return Err(BufferError::VarintNonCanonical);
+        }
+        Ok(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reader(bytes: &[u8]) -> Buffer {
+        Buffer::from_vec(bytes.to_vec())
+    }
+
+    #[test]
+    fn test_uleb128_round_trip() {
+        let mut buf = Buffer::new(256);
+        let values = [0, 1, 127, 128, 300, 16_383, 16_384, u32::MAX as u64, u64::MAX];
+        for v in values {
+            buf.put_uleb128(v).unwrap();
+        }
+        buf.set_pos(0).unwrap();
+        for v in values {
+            assert_eq!(buf.get_uleb128().unwrap(), v);
+        }
+        assert_eq!(buf.remaining(), 0);
+    }
+
+    #[test]
+    fn test_uleb128_max_is_ten_bytes() {
+        let mut buf = Buffer::new(16);
+        buf.put_uleb128(u64::MAX).unwrap();
+        assert_eq!(buf.len(), 10);
+        assert_eq!(buf.as_slice()[9], 0x01);
+    }
+
+    #[test]
+    fn test_uleb128_rejects_bad_encodings() {
+        assert_eq!(
+            reader(&[0x80, 0x00]).get_uleb128(),
+            Err(BufferError::VarintNonCanonical)
+        );
+        let mut too_wide = [0xff; 10];
+        too_wide[9] = 0x02;
+        assert_eq!(
+            reader(&too_wide).get_uleb128(),
+            Err(BufferError::VarintOverflow)
+        );
+        assert_eq!(
+            reader(&[0x80; 11]).get_uleb128(),
+            Err(BufferError::VarintOverflow)
+        );
+        assert_eq!(
+            reader(&[0x80, 0x80]).get_uleb128(),
+            Err(BufferError::overflow("get_uleb128", 0, 3, 2))
+        );
+    }
+
+    #[test]
+    fn test_zigzag_round_trip() {
+        let mut buf = Buffer::new(256);
+        let values = [0, -1, 1, -2, 63, -64, i64::MAX, i64::MIN];
+        for v in values {
+            buf.put_zigzag_leb128(v).unwrap();
+        }
+        assert_eq!(&buf.as_slice()[..4], &[0, 1, 2, 3]);
+        buf.set_pos(0).unwrap();
+        for v in values {
+            assert_eq!(buf.get_zigzag_leb128().unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn test_quic_varint_rfc9000_vectors() {
+        let vectors: [(&[u8], u64); 4] = [
+            (&[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c], 151_288_809_941_952_652),
+            (&[0x9d, 0x7f, 0x3e, 0x7d], 494_878_333),
+            (&[0x7b, 0xbd], 15_293),
+            (&[0x25], 37),
+        ];
+        for (bytes, val) in vectors {
+            assert_eq!(reader(bytes).get_quic_varint().unwrap(), val);
+
+            let mut buf = Buffer::new(8);
+            buf.put_quic_varint(val).unwrap();
+            assert_eq!(buf.as_slice(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_quic_varint_limits() {
+        let mut buf = Buffer::new(8);
+        buf.put_quic_varint(QUIC_VARINT_MAX).unwrap();
+        assert_eq!(
+            buf.put_quic_varint(QUIC_VARINT_MAX + 1),
+            Err(BufferError::VarintOutOfRange)
+        );
+
+        // 37 encoded in two bytes: legal, but not canonical.
+        assert_eq!(reader(&[0x40, 0x25]).get_quic_varint().unwrap(), 37);
+        assert_eq!(
+            reader(&[0x40, 0x25]).get_quic_varint_canonical(),
+            Err(BufferError::VarintNonCanonical)
+        );
+        assert_eq!(
+            reader(&[0x80, 0x00]).get_quic_varint(),
+            Err(BufferError::overflow("get_quic_varint", 0, 4, 2))
+        );
+    }
+}
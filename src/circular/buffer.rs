@@ -27,6 +27,8 @@
 //!   `copy_from_slice` suffices — replaced with a branch-free two-copy path.
 
 use crate::error::{BufferError, Result};
+use alloc::boxed::Box;
+use alloc::vec;
 use zeroize::Zeroize;
 
 /// Maximum circular buffer size (100 MB).
@@ -213,7 +215,7 @@ impl CircularBuffer {
         // `write_pos` must not cross the ring boundary.
         if self.write_pos + len > self.size {
             return Err(BufferError::InvalidState(
-                "write would cross ring boundary — use write_slices_mut or write()".into(),
+                "write would cross ring boundary — use write_slices_mut or write()",
             ));
         }
 
@@ -278,7 +280,12 @@ impl CircularBuffer {
     /// Advances the read cursor after consuming bytes from `read_ptrs`.
     pub fn incr_read(&mut self, len: usize) -> Result<()> {
         if len > self.used {
-            return Err(BufferError::BufferOverflow);
+            return Err(BufferError::overflow(
+                "incr_read",
+                self.read_pos,
+                len,
+                self.used,
+            ));
         }
         let (size, is_pow2) = (self.size, self.is_pow2);
         self.read_pos = wrap(self.read_pos, len, size, is_pow2);
@@ -0,0 +1,132 @@
+// src/circular/bytes_compat.rs
+//! [`bytes`] integration for [`CircularBuffer`] (enabled by the `bytes`
+//! feature).
+//!
+//! The ring exposes at most two contiguous segments, so [`Buf::chunk`] returns
+//! the first and [`Buf::chunks_vectored`] returns both, letting vectored
+//! writers drain a wrapped ring in a single call (`std` feature only).
+
+use super::buffer::CircularBuffer;
+use ::bytes::buf::UninitSlice;
+use ::bytes::{Buf, BufMut};
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+impl Buf for CircularBuffer {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.used()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.read_ptrs().0
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let (first, second) = self.read_ptrs();
+        let mut n = 0;
+        for seg in [first, second] {
+            if seg.is_empty() || n == dst.len() {
+                break;
+            }
+            dst[n] = IoSlice::new(seg);
+            n += 1;
+        }
+        n
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        if self.incr_read(cnt).is_err() {
+            panic!(
+                "advance past end of CircularBuffer: {} > {}",
+                cnt,
+                self.used()
+            );
+        }
+    }
+}
+
+unsafe impl BufMut for CircularBuffer {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        self.available()
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        if self.incr_write(cnt).is_err() {
+            panic!("advance_mut past end of CircularBuffer free space");
+        }
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let room = self.available();
+        let (first, _) = self.write_slices_mut(room).unwrap_or_default();
+        UninitSlice::new(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrapped() -> CircularBuffer {
+        let mut ring = CircularBuffer::new(8);
+        ring.write(&[0; 6]).unwrap();
+        ring.incr_read(6).unwrap();
+        ring.write(b"abcdef").unwrap();
+        ring
+    }
+
+    #[test]
+    fn test_buf_over_wrapped_ring() {
+        let mut ring = wrapped();
+        assert_eq!(Buf::remaining(&ring), 6);
+        assert_eq!(ring.chunk(), b"ab");
+
+        #[cfg(feature = "std")]
+        {
+            let mut slices = [IoSlice::new(&[]); 4];
+            assert_eq!(ring.chunks_vectored(&mut slices), 2);
+            assert_eq!(&*slices[0], b"ab");
+            assert_eq!(&*slices[1], b"cdef");
+        }
+
+        let mut out = [0u8; 6];
+        ring.copy_to_slice(&mut out);
+        assert_eq!(&out, b"abcdef");
+        assert!(ring.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_chunks_vectored_respects_dst_len() {
+        let ring = wrapped();
+        let mut one = [IoSlice::new(&[])];
+        assert_eq!(ring.chunks_vectored(&mut one), 1);
+        assert_eq!(&*one[0], b"ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "advance past end")]
+    fn test_advance_past_end_panics() {
+        let mut ring = wrapped();
+        ring.advance(7);
+    }
+
+    #[test]
+    fn test_buf_mut_wraps_around() {
+        let mut ring = CircularBuffer::new(8);
+        ring.write(&[0; 5]).unwrap();
+        ring.incr_read(5).unwrap();
+
+        ring.put_slice(b"0123456");
+        assert_eq!(ring.remaining_mut(), 1);
+        let (a, b) = ring.read_ptrs();
+        assert_eq!([a, b].concat(), b"0123456");
+    }
+}
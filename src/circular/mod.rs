@@ -2,5 +2,7 @@
 //! Circular (ring) buffer for streaming data
 
 pub mod buffer;
+#[cfg(feature = "bytes")]
+pub(crate) mod bytes_compat;
 
 pub use buffer::CircularBuffer;
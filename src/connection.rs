@@ -141,10 +141,9 @@ impl ConnectionBuffers {
 
     /// Dequeues the next packet for writing to the network.
     pub fn dequeue_packet(&mut self) -> Option<Buffer> {
-        self.packet_queue.pop_front().map(|buf| {
+        self.packet_queue.pop_front().inspect(|buf| {
             self.packet_queue_bytes =
                 self.packet_queue_bytes.saturating_sub(buf.len());
-            buf
         })
     }
 
@@ -0,0 +1,164 @@
+// src/ct.rs
+//! Constant-time comparison and selection over byte slices.
+//!
+//! These back the `ct_*` methods on [`Buffer`](crate::Buffer) and
+//! [`SecretBytes`](crate::SecretBytes), and are exposed for comparing MACs,
+//! tokens and other secrets held in plain slices.
+//!
+//! # Guarantees
+//!
+//! Running time depends only on the *lengths* of the inputs, never on their
+//! contents: there is no early exit on the first differing byte and no
+//! branch on the secret data.  Lengths are treated as public, so comparing
+//! slices of different lengths returns `false` immediately.
+//!
+//! Results are passed through [`core::hint::black_box`] so the optimizer
+//! cannot turn the accumulated masks back into branches.  This is a
+//! best-effort barrier, so the generated code for x86_64 is checked by the
+//! (ignored by default) `ct_codegen` test.
+
+use core::hint::black_box;
+
+/// Returns `true` if `a` and `b` are equal, in time independent of their
+/// contents.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::ct;
+///
+/// assert!(ct::ct_eq(b"mac tag", b"mac tag"));
+/// assert!(!ct::ct_eq(b"mac tag", b"mac taG"));
+/// assert!(!ct::ct_eq(b"mac", b"mac tag"));
+/// ```
+#[inline]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x ^ y;
+    }
+    is_zero_byte(diff)
+}
+
+/// Returns `true` if every byte of `a` is zero, in time independent of its
+/// contents.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::ct;
+///
+/// assert!(ct::ct_is_zero(&[0; 32]));
+/// assert!(!ct::ct_is_zero(&[0, 0, 1, 0]));
+/// ```
+#[inline]
+pub fn ct_is_zero(a: &[u8]) -> bool {
+    let mut acc = 0u8;
+    for x in a {
+        acc |= x;
+    }
+    is_zero_byte(acc)
+}
+
+/// Overwrites `dst` with `src` if `choice` is `true`, and leaves it
+/// unchanged otherwise.  Every byte of `dst` is written either way.
+///
+/// # Panics
+///
+/// Panics if `dst` and `src` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::ct;
+///
+/// let mut key = *b"real";
+/// ct::ct_select(&mut key, b"fake", false);
+/// assert_eq!(&key, b"real");
+/// ct::ct_select(&mut key, b"fake", true);
+/// assert_eq!(&key, b"fake");
+/// ```
+#[inline]
+pub fn ct_select(dst: &mut [u8], src: &[u8], choice: bool) {
+    assert_eq!(dst.len(), src.len(), "ct_select: length mismatch");
+    // 0x00 or 0xFF, hidden from the optimizer so it cannot branch on it.
+    let mask = black_box((choice as u8).wrapping_neg());
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= mask & (*d ^ s);
+    }
+}
+
+/// Maps an accumulated difference to `true` iff it is zero, without a
+/// data-dependent branch.
+#[inline]
+fn is_zero_byte(acc: u8) -> bool {
+    let acc = black_box(acc);
+    // The top bit of `acc | -acc` is set iff `acc != 0`.
+    let nonzero = (acc | acc.wrapping_neg()) >> 7;
+    black_box(nonzero ^ 1) == 1
+}
+
+/// `#[no_mangle]` fixed-size instantiations whose assembly is inspected by
+/// `tests/ct_codegen.rs`.  Compiled only with `--cfg secbuf_ct_probe`.
+#[cfg(secbuf_ct_probe)]
+#[doc(hidden)]
+pub mod probe {
+    #[unsafe(no_mangle)]
+    pub fn secbuf_ct_probe_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+        super::ct_eq(a, b)
+    }
+
+    #[unsafe(no_mangle)]
+    pub fn secbuf_ct_probe_is_zero(a: &[u8; 32]) -> bool {
+        super::ct_is_zero(a)
+    }
+
+    #[unsafe(no_mangle)]
+    pub fn secbuf_ct_probe_select(dst: &mut [u8; 32], src: &[u8; 32], choice: bool) {
+        super::ct_select(dst, src, choice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(&[0xAB; 100], &[0xAB; 100]));
+        for i in 0..100 {
+            let mut other = [0xAB; 100];
+            other[i] ^= 0x01;
+            assert!(!ct_eq(&[0xAB; 100], &other));
+        }
+        assert!(!ct_eq(b"abc", b"abcd"));
+    }
+
+    #[test]
+    fn test_ct_is_zero() {
+        assert!(ct_is_zero(&[]));
+        assert!(ct_is_zero(&[0; 64]));
+        for bit in 0..8 {
+            assert!(!ct_is_zero(&[0, 0, 1 << bit]));
+        }
+    }
+
+    #[test]
+    fn test_ct_select() {
+        let mut dst = [1u8, 2, 3];
+        ct_select(&mut dst, &[7, 8, 9], false);
+        assert_eq!(dst, [1, 2, 3]);
+        ct_select(&mut dst, &[7, 8, 9], true);
+        assert_eq!(dst, [7, 8, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "length mismatch")]
+    fn test_ct_select_length_mismatch() {
+        ct_select(&mut [0u8; 2], &[0u8; 3], true);
+    }
+}
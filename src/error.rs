@@ -1,6 +1,12 @@
 //! Error types for buffer operations with advanced conversion support
+//!
+//! Errors raised on the parsing hot path carry only `&'static str` and
+//! integer context, so building one never allocates.  Use
+//! [`BufferError::kind`] to match on the category without caring about the
+//! context fields.
 
-use std::fmt;
+use alloc::string::String;
+use core::fmt;
 
 /// Errors that can occur during buffer operations
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,9 +18,18 @@ pub enum BufferError {
     /// Buffer size exceeds maximum allowed
     SizeTooBig,
     /// Attempted to read/write beyond buffer bounds
-    BufferOverflow,
+    BufferOverflow {
+        /// Operation that failed, e.g. `"get_u32"`
+        op: &'static str,
+        /// Position at which it was attempted
+        offset: usize,
+        /// Bytes the operation needed
+        requested: usize,
+        /// Bytes that were available
+        available: usize,
+    },
     /// Invalid buffer state
-    InvalidState(String),
+    InvalidState(&'static str),
     /// Circular buffer is full
     BufferFull,
     /// Circular buffer is empty
@@ -24,9 +39,128 @@ pub enum BufferError {
     /// Invalid string encoding
     InvalidString,
     /// Invalid data format
-    InvalidData(String),
+    InvalidData(&'static str),
     /// I/O error (for compatibility)
     Io(String),
+    /// Varint encodes a value wider than 64 bits
+    VarintOverflow,
+    /// Varint uses more bytes than its value requires
+    VarintNonCanonical,
+    /// Value exceeds the QUIC varint maximum of 2^62 - 1
+    VarintOutOfRange,
+    /// Locking memory failed because it would exceed `RLIMIT_MEMLOCK`
+    MemlockLimitExceeded {
+        /// Bytes that were being locked
+        requested: usize,
+        /// Soft `RLIMIT_MEMLOCK` in bytes (`u64::MAX` if unlimited or unknown)
+        limit: u64,
+    },
+    /// Payload is too long for its length prefix
+    LengthPrefixOverflow {
+        /// Payload length in bytes
+        len: usize,
+        /// Prefix width in bytes
+        width: usize,
+    },
+    /// Error converted from another library's error type
+    Other(String),
+}
+
+/// The category of a [`BufferError`], without its context.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::{Buffer, ErrorKind};
+///
+/// let mut buf = Buffer::from_vec(vec![0, 1]);
+/// let err = buf.get_u32().unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::BufferOverflow);
+/// assert_eq!(
+///     err.to_string(),
+///     "Buffer overflow in get_u32: needed 4 bytes at offset 0, 2 available"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// See [`BufferError::PositionOutOfBounds`]
+    PositionOutOfBounds,
+    /// See [`BufferError::IncrementTooLarge`]
+    IncrementTooLarge,
+    /// See [`BufferError::SizeTooBig`]
+    SizeTooBig,
+    /// See [`BufferError::BufferOverflow`]
+    BufferOverflow,
+    /// See [`BufferError::InvalidState`]
+    InvalidState,
+    /// See [`BufferError::BufferFull`]
+    BufferFull,
+    /// See [`BufferError::BufferEmpty`]
+    BufferEmpty,
+    /// See [`BufferError::InsufficientSpace`]
+    InsufficientSpace,
+    /// See [`BufferError::InvalidString`]
+    InvalidString,
+    /// See [`BufferError::InvalidData`]
+    InvalidData,
+    /// See [`BufferError::Io`]
+    Io,
+    /// See [`BufferError::VarintOverflow`]
+    VarintOverflow,
+    /// See [`BufferError::VarintNonCanonical`]
+    VarintNonCanonical,
+    /// See [`BufferError::VarintOutOfRange`]
+    VarintOutOfRange,
+    /// See [`BufferError::MemlockLimitExceeded`]
+    MemlockLimitExceeded,
+    /// See [`BufferError::LengthPrefixOverflow`]
+    LengthPrefixOverflow,
+    /// See [`BufferError::Other`]
+    Other,
+}
+
+impl BufferError {
+    /// Builds a [`BufferOverflow`](Self::BufferOverflow) error.
+    ///
+    /// Kept out of line so the bounds checks that call it stay small.
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn overflow(
+        op: &'static str,
+        offset: usize,
+        requested: usize,
+        available: usize,
+    ) -> Self {
+        Self::BufferOverflow {
+            op,
+            offset,
+            requested,
+            available,
+        }
+    }
+
+    /// Returns the error's category.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::PositionOutOfBounds => ErrorKind::PositionOutOfBounds,
+            Self::IncrementTooLarge => ErrorKind::IncrementTooLarge,
+            Self::SizeTooBig => ErrorKind::SizeTooBig,
+            Self::BufferOverflow { .. } => ErrorKind::BufferOverflow,
+            Self::InvalidState(_) => ErrorKind::InvalidState,
+            Self::BufferFull => ErrorKind::BufferFull,
+            Self::BufferEmpty => ErrorKind::BufferEmpty,
+            Self::InsufficientSpace => ErrorKind::InsufficientSpace,
+            Self::InvalidString => ErrorKind::InvalidString,
+            Self::InvalidData(_) => ErrorKind::InvalidData,
+            Self::Io(_) => ErrorKind::Io,
+            Self::VarintOverflow => ErrorKind::VarintOverflow,
+            Self::VarintNonCanonical => ErrorKind::VarintNonCanonical,
+            Self::VarintOutOfRange => ErrorKind::VarintOutOfRange,
+            Self::MemlockLimitExceeded { .. } => ErrorKind::MemlockLimitExceeded,
+            Self::LengthPrefixOverflow { .. } => ErrorKind::LengthPrefixOverflow,
+            Self::Other(_) => ErrorKind::Other,
+        }
+    }
 }
 
 impl fmt::Display for BufferError {
@@ -35,7 +169,16 @@ impl fmt::Display for BufferError {
             Self::PositionOutOfBounds => write!(f, "Position out of bounds"),
             Self::IncrementTooLarge => write!(f, "Increment too large"),
             Self::SizeTooBig => write!(f, "Buffer size too big"),
-            Self::BufferOverflow => write!(f, "Buffer overflow"),
+            Self::BufferOverflow {
+                op,
+                offset,
+                requested,
+                available,
+            } => write!(
+                f,
+                "Buffer overflow in {}: needed {} bytes at offset {}, {} available",
+                op, requested, offset, available
+            ),
             Self::InvalidState(msg) => write!(f, "Invalid state: {}", msg),
             Self::BufferFull => write!(f, "Circular buffer is full"),
             Self::BufferEmpty => write!(f, "Circular buffer is empty"),
@@ -43,22 +186,37 @@ impl fmt::Display for BufferError {
             Self::InvalidString => write!(f, "Invalid string encoding"),
             Self::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
             Self::Io(msg) => write!(f, "I/O error: {}", msg),
+            Self::VarintOverflow => write!(f, "Varint exceeds 64-bit range"),
+            Self::VarintNonCanonical => write!(f, "Non-canonical (over-long) varint encoding"),
+            Self::VarintOutOfRange => write!(f, "Value exceeds QUIC varint maximum (2^62 - 1)"),
+            Self::MemlockLimitExceeded { requested, limit } => write!(
+                f,
+                "Cannot lock {} bytes: RLIMIT_MEMLOCK is {} bytes (raise `ulimit -l` or grant CAP_IPC_LOCK)",
+                requested, limit
+            ),
+            Self::LengthPrefixOverflow { len, width } => write!(
+                f,
+                "Payload of {} bytes does not fit in a {}-byte length prefix",
+                len, width
+            ),
+            Self::Other(msg) => write!(f, "{}", msg),
         }
     }
 }
 
-impl std::error::Error for BufferError {}
+impl core::error::Error for BufferError {}
 
 // ============================================================================
 // ADVANCED ERROR CONVERSION - Makes buffer library compatible with any error type
 // ============================================================================
 
 /// Convert BufferError to std::io::Error
+#[cfg(feature = "std")]
 impl From<BufferError> for std::io::Error {
     fn from(err: BufferError) -> Self {
         use std::io::ErrorKind;
         match err {
-            BufferError::BufferOverflow | BufferError::InsufficientSpace => {
+            BufferError::BufferOverflow { .. } | BufferError::InsufficientSpace => {
                 std::io::Error::new(ErrorKind::WriteZero, err)
             }
             BufferError::BufferEmpty => std::io::Error::new(ErrorKind::UnexpectedEof, err),
@@ -69,6 +227,7 @@ impl From<BufferError> for std::io::Error {
 }
 
 /// Convert std::io::Error to BufferError
+#[cfg(feature = "std")]
 impl From<std::io::Error> for BufferError {
     fn from(err: std::io::Error) -> Self {
         BufferError::Io(err.to_string())
@@ -83,7 +242,23 @@ impl From<std::io::Error> for BufferError {
 #[cfg(feature = "anyhow")]
 impl From<anyhow::Error> for BufferError {
     fn from(err: anyhow::Error) -> Self {
-        BufferError::InvalidState(err.to_string())
+        BufferError::Other(err.to_string())
+    }
+}
+
+/// Lets [`BufferError`] carry serde's custom errors (e.g. an unknown enum
+/// variant) from [`crate::ssh`].
+#[cfg(feature = "serde")]
+impl serde::ser::Error for BufferError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        BufferError::Other(alloc::string::ToString::to_string(&msg))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for BufferError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        BufferError::Other(alloc::string::ToString::to_string(&msg))
     }
 }
 
@@ -95,7 +270,7 @@ impl From<anyhow::Error> for BufferError {
 ///
 /// Note: When using with other Result types (like anyhow::Result),
 /// either qualify the type (`buffer::Result<T>`) or use the conversion traits.
-pub type Result<T> = std::result::Result<T, BufferError>;
+pub type Result<T> = core::result::Result<T, BufferError>;
 
 // ============================================================================
 // EXTENSION TRAIT FOR EASY CONVERSION
@@ -108,6 +283,7 @@ pub trait ResultExt<T> {
     fn into_anyhow(self) -> anyhow::Result<T>;
 
     /// Convert to io::Result
+    #[cfg(feature = "std")]
     fn into_io(self) -> std::io::Result<T>;
 }
 
@@ -117,6 +293,7 @@ impl<T> ResultExt<T> for Result<T> {
         self.map_err(|e| e.into())
     }
 
+    #[cfg(feature = "std")]
     fn into_io(self) -> std::io::Result<T> {
         self.map_err(|e| e.into())
     }
@@ -170,13 +347,30 @@ macro_rules! buffer_try {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_error_conversion_io() {
-        let buf_err = BufferError::BufferOverflow;
+        let buf_err = BufferError::overflow("put_u32", 6, 4, 2);
         let io_err: std::io::Error = buf_err.into();
         assert_eq!(io_err.kind(), std::io::ErrorKind::WriteZero);
     }
 
+    #[test]
+    fn test_kind_ignores_context() {
+        let a = BufferError::overflow("get_u16", 0, 2, 1);
+        let b = BufferError::overflow("get_bytes", 10, 100, 0);
+        assert_ne!(a, b);
+        assert_eq!(a.kind(), b.kind());
+        assert_eq!(BufferError::InvalidData("x").kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_error_stays_small() {
+        // No heap-owning payload on the hot path; keep `Result<u8>` compact.
+        assert!(core::mem::size_of::<BufferError>() <= 48);
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_result_ext() {
         let result: Result<u32> = Ok(42);
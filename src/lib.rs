@@ -12,36 +12,87 @@
 //! - Optional SIMD acceleration for bulk operations
 //! - Automatic secure erasure on connection termination (mimics Dropbear behavior)
 
+//!
+//! ## `no_std`
+//!
+//! With the default `std` feature disabled, the [`buffer`], [`circular`],
+//! [`ct`], [`error`] and [`secret`] modules build on `core` + `alloc`.
+//! Pools, connection management, locked memory and the `std::io` trait
+//! implementations require `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 #![allow(clippy::missing_safety_doc)]
 
+extern crate alloc;
+
 pub mod buffer;
 pub mod circular;
+#[cfg(feature = "std")]
 pub mod connection;
+pub mod ct;
 pub mod error;
+#[cfg(all(unix, feature = "std"))]
+pub mod locked;
+#[cfg(feature = "std")]
 pub mod pool;
+pub mod secret;
+#[cfg(feature = "serde")]
+pub mod ssh;
 
 // Re-export main types
-pub use buffer::Buffer;
+pub use buffer::{
+    AllocMode, ArrayBuffer, Buffer, BufferRead, BufferWrite, Checkpoint, Decode, Encode,
+    FrozenBuffer, GrowthPolicy, LengthPrefixed, PrefixWidth, StreamBuffer, StreamReader,
+    StreamWriter, SubReader,
+};
+#[doc(hidden)]
+pub use buffer::codec::__private;
 pub use circular::CircularBuffer;
+#[cfg(feature = "std")]
 pub use connection::{ConnectionBufferConfig, ConnectionBuffers, PooledConnectionBuffers};
-pub use error::{BufferError, Result};
+pub use error::{BufferError, ErrorKind, Result};
+#[cfg(all(unix, feature = "std"))]
+pub use locked::{Enclave, EnclaveGuard, LockedBuffer, SealedBuffer};
+#[cfg(all(unix, feature = "std"))]
+pub use pool::{LockedBufferPool, PooledLockedBuffer};
+#[cfg(feature = "std")]
 pub use pool::{
     BufferPool, FastBufferPool, FastPoolStats, FastPooledBuffer, PoolConfig, PoolStats,
     PooledBuffer,
 };
+pub use secret::SecretBytes;
+#[cfg(feature = "derive")]
+pub use secbuf_derive::{Decode, Encode};
 
 /// Commonly used imports.
+///
+/// [`BufferRead`] and [`BufferWrite`] are not included: their methods share
+/// names with `bytes::Buf`/`BufMut`, which are implemented for some of the
+/// same types, so glob-importing both would make those calls ambiguous.
+/// Import the traits by name where generic code needs them.
 pub mod prelude {
-    pub use crate::buffer::Buffer;
+    pub use crate::buffer::{
+        AllocMode, ArrayBuffer, Buffer, Checkpoint, Decode, Encode, FrozenBuffer, GrowthPolicy,
+        LengthPrefixed, PrefixWidth, StreamBuffer, StreamReader, StreamWriter, SubReader,
+    };
     pub use crate::circular::CircularBuffer;
+    #[cfg(feature = "std")]
     pub use crate::connection::{ConnectionBufferConfig, ConnectionBuffers, PooledConnectionBuffers};
     pub use crate::error::{BufferError, Result};
+    #[cfg(all(unix, feature = "std"))]
+    pub use crate::locked::{Enclave, EnclaveGuard, LockedBuffer, SealedBuffer};
+    #[cfg(all(unix, feature = "std"))]
+    pub use crate::pool::{LockedBufferPool, PooledLockedBuffer};
+    #[cfg(feature = "std")]
     pub use crate::pool::{
         BufferPool, FastBufferPool, FastPoolStats, FastPooledBuffer, PoolConfig, PoolStats,
         PooledBuffer,
     };
+    pub use crate::secret::SecretBytes;
+    #[cfg(feature = "derive")]
+    pub use secbuf_derive::{Decode, Encode};
 }
 
 #[cfg(test)]
@@ -59,12 +110,14 @@ mod tests {
         assert_eq!(buf.get_byte().unwrap(), 0xFF);
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_standard_pool() {
         let pool = BufferPool::new(PoolConfig {
             buffer_size: 1024,
             max_pool_size: 100,
             min_pool_size: 10,
+            ..PoolConfig::default()
         });
 
         let mut buffers = Vec::new();
@@ -80,12 +133,14 @@ mod tests {
         assert!(stats.total_acquired >= 50);
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_fast_pool() {
         let pool = FastBufferPool::new(PoolConfig {
             buffer_size: 1024,
             max_pool_size: 100,
             min_pool_size: 10,
+            ..PoolConfig::default()
         });
 
         let mut buffers = Vec::new();
@@ -0,0 +1,176 @@
+// src/locked/buffer.rs
+//! [`LockedBuffer`]: a [`Buffer`] whose storage is locked in RAM.
+
+use super::region::LockedRegion;
+use crate::buffer::Buffer;
+use crate::buffer::storage::Storage;
+use crate::error::{BufferError, Result};
+
+/// A [`Buffer`] backed by page-aligned memory that is `mlock`ed (never
+/// swapped) and excluded from core dumps (`MADV_DONTDUMP` on Linux).
+///
+/// `LockedBuffer` dereferences to [`Buffer`], so the full read/write API is
+/// available unchanged.  Growth (via
+/// [`set_growth_policy`](Buffer::set_growth_policy), `resize`, …) moves the
+/// data into a new locked region and wipes the old one.
+///
+/// On drop the whole mapping is zeroized while still locked, and only then
+/// unlocked and unmapped.
+///
+/// Locked memory is a scarce resource: the total per process is capped by
+/// `RLIMIT_MEMLOCK` (often 64 KiB–8 MiB for unprivileged users), and every
+/// buffer occupies at least one page.  Prefer a [`LockedBufferPool`] over
+/// creating many short-lived buffers.
+///
+/// [`LockedBufferPool`]: crate::LockedBufferPool
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::LockedBuffer;
+/// # use secbuf::BufferError;
+///
+/// let mut key = LockedBuffer::new(32)?;
+/// key.put_bytes(&[0x42; 32])?;
+/// assert!(key.is_locked());
+/// # Ok::<(), BufferError>(())
+/// ```
+pub struct LockedBuffer {
+    inner: Buffer,
+}
+
+impl LockedBuffer {
+    /// Creates a locked buffer with `size` zeroed, writable bytes, like
+    /// [`Buffer::new`].
+    ///
+    /// # Errors
+    ///
+    /// - [`BufferError::SizeTooBig`] if `size` exceeds
+    ///   [`BUF_MAX_SIZE`](crate::buffer::core::BUF_MAX_SIZE).
+    /// - [`BufferError::MemlockLimitExceeded`] if the pages cannot be locked.
+    /// - [`BufferError::Io`] if the memory cannot be mapped.
+    pub fn new(size: usize) -> Result<Self> {
+        Self::check_size(size)?;
+        let mut inner = Buffer::from_storage(Storage::Locked(LockedRegion::zeroed(size)?));
+        inner.len = 0;
+        Ok(Self { inner })
+    }
+
+    /// Creates an empty locked buffer with room for `capacity` bytes, like
+    /// [`Buffer::with_capacity`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`](Self::new).
+    pub fn with_capacity(capacity: usize) -> Result<Self> {
+        Self::check_size(capacity)?;
+        let inner = Buffer::from_storage(Storage::Locked(LockedRegion::with_capacity(capacity)?));
+        Ok(Self { inner })
+    }
+
+    fn check_size(size: usize) -> Result<()> {
+        if size > crate::buffer::core::BUF_MAX_SIZE {
+            return Err(BufferError::SizeTooBig);
+        }
+        Ok(())
+    }
+
+    /// Copies the buffer into a new locked region.
+    ///
+    /// Unlike cloning the inner [`Buffer`], which panics if the copy cannot
+    /// be locked, this reports the failure.
+    pub fn try_clone(&self) -> Result<Self> {
+        let Storage::Locked(region) = &self.inner.data else {
+            return Err(BufferError::InvalidState(
+                "LockedBuffer storage was swapped for unlocked memory",
+            ));
+        };
+        let mut inner = Buffer::from_storage(Storage::Locked(region.try_clone()?));
+        inner.pos = self.inner.pos;
+        inner.len = self.inner.len;
+        inner.growth = self.inner.growth;
+        Ok(Self { inner })
+    }
+}
+
+impl std::ops::Deref for LockedBuffer {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for LockedBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl AsRef<[u8]> for LockedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GrowthPolicy;
+    use crate::locked::region::page_size;
+
+    #[test]
+    fn test_locked_buffer_round_trip() {
+        let mut buf = LockedBuffer::new(64).unwrap();
+        assert!(buf.is_locked());
+        assert_eq!(buf.capacity(), page_size());
+        assert_eq!(buf.as_slice().as_ptr() as usize % page_size(), 0);
+
+        buf.put_u32(0xDEAD_BEEF).unwrap();
+        buf.put_string(b"private key").unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(buf.get_u32().unwrap(), 0xDEAD_BEEF);
+        assert_eq!(buf.get_string_ref().unwrap(), b"private key");
+    }
+
+    #[test]
+    fn test_locked_buffer_is_fixed_size_by_default() {
+        let mut buf = LockedBuffer::new(8).unwrap();
+        buf.put_u64(1).unwrap();
+        assert!(matches!(
+            buf.put_byte(0),
+            Err(BufferError::BufferOverflow { op: "put_byte", .. })
+        ));
+    }
+
+    #[test]
+    fn test_locked_growth_stays_locked() {
+        let mut buf = LockedBuffer::with_capacity(16).unwrap();
+        buf.set_growth_policy(GrowthPolicy::Doubling { max: 3 * page_size() })
+            .unwrap();
+        buf.put_bytes(&vec![0x11; 2 * page_size()]).unwrap();
+        assert!(buf.is_locked());
+        assert!(buf.capacity() >= 2 * page_size());
+        assert_eq!(buf.as_slice()[2 * page_size() - 1], 0x11);
+    }
+
+    #[test]
+    fn test_try_clone_and_into_vec() {
+        let mut buf = LockedBuffer::new(32).unwrap();
+        buf.put_bytes(b"secret").unwrap();
+        let copy = buf.try_clone().unwrap();
+        assert!(copy.is_locked());
+        assert_eq!(copy.as_slice(), b"secret");
+        assert_eq!(copy.pos(), 6);
+
+        let plain = (*buf).clone().into_vec();
+        assert_eq!(plain, b"secret");
+    }
+
+    #[test]
+    fn test_size_limit() {
+        assert!(matches!(
+            LockedBuffer::new(crate::buffer::core::BUF_MAX_SIZE + 1),
+            Err(BufferError::SizeTooBig)
+        ));
+    }
+}
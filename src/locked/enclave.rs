@@ -0,0 +1,249 @@
+// src/locked/enclave.rs
+//! [`Enclave`]: buffers that are encrypted at rest in process memory.
+
+use super::chacha20::{self, KEY_LEN, NONCE_LEN};
+use super::guarded::os_random;
+use super::region::LockedRegion;
+use crate::buffer::Buffer;
+use crate::error::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// The per-process enclave key, in its own locked, dump-excluded page.
+///
+/// Only a successfully created key is cached: a failure (e.g. a transient
+/// `RLIMIT_MEMLOCK` shortage) is returned and retried on the next call.
+fn process_key() -> Result<&'static [u8; KEY_LEN]> {
+    static KEY: OnceLock<LockedRegion> = OnceLock::new();
+    static INIT: Mutex<()> = Mutex::new(());
+    let region = get_or_try_init(&KEY, &INIT, || {
+        let mut region = LockedRegion::zeroed(KEY_LEN)?;
+        os_random(region.as_mut_slice())?;
+        Ok(region)
+    })?;
+    Ok(region.as_slice().try_into().expect("key region is KEY_LEN bytes"))
+}
+
+/// Returns the value in `cell`, running `init` to fill it if it is empty.
+///
+/// `lock` serializes initialization so `init` runs at most once at a time;
+/// unlike [`OnceLock::get_or_init`], an error leaves the cell empty.
+fn get_or_try_init<'a, T>(
+    cell: &'a OnceLock<T>,
+    lock: &Mutex<()>,
+    init: impl FnOnce() -> Result<T>,
+) -> Result<&'a T> {
+    if let Some(value) = cell.get() {
+        return Ok(value);
+    }
+    let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(value) = cell.get() {
+        return Ok(value);
+    }
+    let value = init()?;
+    Ok(cell.get_or_init(|| value))
+}
+
+/// Returns a nonce never used before in this process.
+///
+/// A process-wide counter guarantees the keystream is never reused under
+/// the process key, however many enclaves are sealed and resealed.
+fn next_nonce() -> [u8; NONCE_LEN] {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[..8].copy_from_slice(&n.to_le_bytes());
+    nonce
+}
+
+/// A buffer whose contents are kept ChaCha20-encrypted in memory.
+///
+/// The key is random per process and lives in a separate `mlock`ed page, so
+/// a memory-disclosure bug that reads the enclave's storage sees only
+/// ciphertext.  [`open`](Self::open) decrypts into a temporary [`Buffer`]
+/// that exists only as long as the returned [`EnclaveGuard`]; dropping the
+/// guard re-encrypts the (possibly modified) contents under a fresh nonce
+/// and zeroizes the plaintext.
+///
+/// This protects data at rest, not against an attacker who can read the key
+/// page or observe the process while a guard is open.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::Enclave;
+/// # use secbuf::BufferError;
+///
+/// let mut enclave = Enclave::new(64)?;
+/// enclave.open().put_bytes(b"api token")?;
+///
+/// let guard = enclave.open();
+/// assert_eq!(guard.as_slice(), b"api token");
+/// // Re-encrypted when `guard` drops.
+/// # Ok::<(), BufferError>(())
+/// ```
+pub struct Enclave {
+    /// Ciphertext, with the plaintext buffer's position, length and growth
+    /// policy.
+    sealed: Buffer,
+    nonce: [u8; NONCE_LEN],
+}
+
+impl Enclave {
+    /// Creates an empty enclave with `size` writable bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process key does not exist yet and cannot be
+    /// created or locked, e.g.
+    /// [`BufferError::MemlockLimitExceeded`](crate::BufferError::MemlockLimitExceeded).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` exceeds
+    /// [`BUF_MAX_SIZE`](crate::buffer::core::BUF_MAX_SIZE), like
+    /// [`Buffer::new`].
+    pub fn new(size: usize) -> Result<Self> {
+        Self::seal(Buffer::new(size))
+    }
+
+    /// Encrypts `buf` in place and takes ownership of it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`](Self::new).
+    pub fn seal(mut buf: Buffer) -> Result<Self> {
+        let key = process_key()?;
+        let nonce = next_nonce();
+        chacha20::apply_keystream(key, &nonce, 0, &mut buf.data);
+        Ok(Self { sealed: buf, nonce })
+    }
+
+    /// Decrypts the contents into a temporary buffer.
+    ///
+    /// The guard dereferences to [`Buffer`]; changes made through it are
+    /// kept when it is dropped and the enclave is resealed.
+    pub fn open(&mut self) -> EnclaveGuard<'_> {
+        // The key exists: it was created when this enclave was sealed.
+        let key = process_key().expect("enclave key initialised at seal");
+        let mut plain = self.sealed.clone();
+        chacha20::apply_keystream(key, &self.nonce, 0, &mut plain.data);
+        EnclaveGuard {
+            enclave: self,
+            plain,
+        }
+    }
+
+    /// Valid data length.  Does not decrypt the contents.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sealed.len()
+    }
+
+    /// Returns `true` if the enclave holds no valid data.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sealed.is_empty()
+    }
+}
+
+/// Plaintext access to an [`Enclave`], returned by [`Enclave::open`].
+///
+/// Dereferences to the decrypted [`Buffer`].  On drop the contents are
+/// re-encrypted under a fresh nonce and the plaintext is zeroized.
+pub struct EnclaveGuard<'a> {
+    enclave: &'a mut Enclave,
+    plain: Buffer,
+}
+
+impl std::ops::Deref for EnclaveGuard<'_> {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        &self.plain
+    }
+}
+
+impl std::ops::DerefMut for EnclaveGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.plain
+    }
+}
+
+impl Drop for EnclaveGuard<'_> {
+    fn drop(&mut self) {
+        let key = process_key().expect("enclave key initialised at seal");
+        let nonce = next_nonce();
+        // Encrypt in place, then swap: the old ciphertext ends up in
+        // `self.plain` and is zeroized when the guard's fields drop.
+        chacha20::apply_keystream(key, &nonce, 0, &mut self.plain.data);
+        std::mem::swap(&mut self.enclave.sealed, &mut self.plain);
+        self.enclave.nonce = nonce;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BufferError;
+
+    #[test]
+    fn test_contents_are_encrypted_at_rest() {
+        let mut enclave = Enclave::new(32).unwrap();
+        enclave.open().put_bytes(&[0u8; 32]).unwrap();
+
+        // All-zero plaintext: the stored bytes are exactly the keystream.
+        assert_eq!(enclave.len(), 32);
+        assert_ne!(enclave.sealed.as_slice(), &[0u8; 32]);
+
+        let guard = enclave.open();
+        assert_eq!(guard.as_slice(), &[0u8; 32]);
+    }
+
+    #[test]
+    fn test_reseal_uses_fresh_nonce() {
+        let mut enclave = Enclave::seal(Buffer::from_vec(b"constant".to_vec())).unwrap();
+        let first = enclave.sealed.as_slice().to_vec();
+        drop(enclave.open());
+        assert_ne!(enclave.sealed.as_slice(), &first[..]);
+        assert_eq!(enclave.open().as_slice(), b"constant");
+    }
+
+    #[test]
+    fn test_guard_preserves_cursor_and_growth() {
+        let mut enclave = Enclave::seal(Buffer::growable(4, 256)).unwrap();
+        {
+            let mut guard = enclave.open();
+            guard.put_u32(1).unwrap();
+            guard.put_bytes(&[0xCC; 100]).unwrap();
+        }
+        let mut guard = enclave.open();
+        assert_eq!(guard.pos(), 104);
+        guard.set_pos(0).unwrap();
+        assert_eq!(guard.get_u32().unwrap(), 1);
+        assert_eq!(guard.get_bytes_ref(100).unwrap(), &[0xCC; 100]);
+    }
+
+    #[test]
+    fn test_failed_key_init_is_retried() {
+        let cell = OnceLock::new();
+        let lock = Mutex::new(());
+        let err = BufferError::MemlockLimitExceeded {
+            requested: 4096,
+            limit: 0,
+        };
+        assert_eq!(
+            get_or_try_init(&cell, &lock, || Err(err.clone())),
+            Err(err)
+        );
+        assert!(cell.get().is_none());
+        assert_eq!(get_or_try_init(&cell, &lock, || Ok(7)), Ok(&7));
+        assert_eq!(get_or_try_init(&cell, &lock, || Ok(8)), Ok(&7));
+    }
+
+    #[test]
+    fn test_nonces_are_unique() {
+        let a = next_nonce();
+        let b = next_nonce();
+        assert_ne!(a, b);
+    }
+}
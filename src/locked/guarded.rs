@@ -0,0 +1,227 @@
+// src/locked/guarded.rs
+//! Guard-page allocations in the style of libsodium's `sodium_malloc`.
+//!
+//! ```text
+//! | guard (PROT_NONE) | … canary | data ……………… | guard (PROT_NONE) |
+//!                                 ^ right-aligned so the first byte past
+//!                                   capacity faults immediately
+//! ```
+//!
+//! Overflowing the data faults on the trailing guard page.  Underflowing it
+//! corrupts the canary, which is checked on drop; a mismatch aborts the
+//! process rather than let execution continue on a corrupted heap.
+
+use super::region::{page_size, round_to_pages};
+use crate::error::Result;
+use std::io::{self, Read};
+use std::ptr::NonNull;
+use std::sync::OnceLock;
+use zeroize::Zeroize;
+
+/// Bytes of canary placed immediately before the data.
+pub(crate) const CANARY_LEN: usize = 16;
+
+/// Fills `out` from the operating system's CSPRNG.
+pub(crate) fn os_random(out: &mut [u8]) -> io::Result<()> {
+    std::fs::File::open("/dev/urandom")?.read_exact(out)
+}
+
+/// Per-process random canary value.
+fn canary() -> &'static [u8; CANARY_LEN] {
+    static CANARY: OnceLock<[u8; CANARY_LEN]> = OnceLock::new();
+    CANARY.get_or_init(|| {
+        let mut bytes = [0u8; CANARY_LEN];
+        os_random(&mut bytes).expect("failed to read /dev/urandom for guard canary");
+        bytes
+    })
+}
+
+/// An anonymous mapping with `PROT_NONE` guard pages on either side of the
+/// data and a canary in front of it.
+pub(crate) struct GuardedRegion {
+    /// Start of the whole mapping (the leading guard page).
+    base: NonNull<u8>,
+    /// Size of the whole mapping, guard pages included.
+    map_len: usize,
+    /// Offset of the first data byte from `base`.
+    data_off: usize,
+    capacity: usize,
+    len: usize,
+}
+
+// SAFETY: the region exclusively owns its mapping, like a `Vec<u8>`.
+unsafe impl Send for GuardedRegion {}
+unsafe impl Sync for GuardedRegion {}
+
+impl GuardedRegion {
+    /// Maps a guarded region with room for exactly `capacity` bytes and a
+    /// logical length of 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::Io`](crate::BufferError::Io) if the mapping or
+    /// the guard-page protection fails.
+    pub(crate) fn with_capacity(capacity: usize) -> Result<Self> {
+        let page = page_size();
+        let inner_len = round_to_pages(CANARY_LEN + capacity);
+        let map_len = inner_len + 2 * page;
+
+        let base = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                map_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANON,
+                -1,
+                0,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error().into());
+        }
+        let base = base.cast::<u8>();
+
+        let protect = |ptr: *mut u8| unsafe {
+            libc::mprotect(ptr.cast(), page, libc::PROT_NONE) == 0
+        };
+        if !protect(base) || !protect(unsafe { base.add(page + inner_len) }) {
+            let err = io::Error::last_os_error();
+            unsafe { libc::munmap(base.cast(), map_len) };
+            return Err(err.into());
+        }
+
+        let data_off = page + inner_len - capacity;
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                canary().as_ptr(),
+                base.add(data_off - CANARY_LEN),
+                CANARY_LEN,
+            );
+        }
+
+        Ok(Self {
+            // SAFETY: mmap never returns null on success.
+            base: unsafe { NonNull::new_unchecked(base) },
+            map_len,
+            data_off,
+            capacity,
+            len: 0,
+        })
+    }
+
+    /// Maps a guarded region of `len` zeroed bytes.
+    pub(crate) fn zeroed(len: usize) -> Result<Self> {
+        let mut region = Self::with_capacity(len)?;
+        // Anonymous mappings are zero-filled by the kernel.
+        region.len = len;
+        Ok(region)
+    }
+
+    /// Allocates a new guarded region holding a copy of this one.
+    pub(crate) fn try_clone(&self) -> Result<Self> {
+        let mut copy = Self::with_capacity(self.capacity)?;
+        copy.resize(self.len);
+        copy.as_mut_slice().copy_from_slice(self.as_slice());
+        Ok(copy)
+    }
+
+    #[inline]
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    fn data_ptr(&self) -> *mut u8 {
+        unsafe { self.base.as_ptr().add(self.data_off) }
+    }
+
+    /// Sets the logical length, zero-filling any newly exposed bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` exceeds [`capacity`](Self::capacity).
+    pub(crate) fn resize(&mut self, new_len: usize) {
+        assert!(
+            new_len <= self.capacity,
+            "guarded region resize to {} exceeds capacity {}",
+            new_len,
+            self.capacity
+        );
+        if new_len > self.len {
+            unsafe { std::ptr::write_bytes(self.data_ptr().add(self.len), 0, new_len - self.len) };
+        }
+        self.len = new_len;
+    }
+
+    /// Shortens the logical length; the discarded bytes are left in place.
+    #[inline]
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        self.len = self.len.min(new_len);
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data_ptr(), self.len) }
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.data_ptr(), self.len) }
+    }
+
+    /// Zeroizes the whole data area and sets the length to 0.
+    pub(crate) fn wipe(&mut self) {
+        self.len = self.capacity;
+        self.as_mut_slice().zeroize();
+        self.len = 0;
+    }
+
+    /// Returns `true` if the canary in front of the data is intact.
+    pub(crate) fn canary_intact(&self) -> bool {
+        let stored =
+            unsafe { std::slice::from_raw_parts(self.data_ptr().sub(CANARY_LEN), CANARY_LEN) };
+        stored == canary()
+    }
+}
+
+impl Drop for GuardedRegion {
+    fn drop(&mut self) {
+        if !self.canary_intact() {
+            eprintln!("secbuf: guarded buffer canary corrupted (buffer underflow); aborting");
+            std::process::abort();
+        }
+        self.wipe();
+        unsafe { libc::munmap(self.base.as_ptr().cast(), self.map_len) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_is_right_aligned_against_guard() {
+        let region = GuardedRegion::zeroed(100).unwrap();
+        let end = region.as_slice().as_ptr() as usize + 100;
+        assert_eq!(end % page_size(), 0);
+        assert_eq!(region.as_slice(), &[0u8; 100][..]);
+        assert!(region.canary_intact());
+    }
+
+    #[test]
+    fn test_zero_capacity_region() {
+        let mut region = GuardedRegion::with_capacity(0).unwrap();
+        assert!(region.as_slice().is_empty());
+        region.resize(0);
+        assert!(region.canary_intact());
+    }
+
+    #[test]
+    fn test_try_clone_copies_contents() {
+        let mut region = GuardedRegion::zeroed(5).unwrap();
+        region.as_mut_slice().copy_from_slice(b"guard");
+        let copy = region.try_clone().unwrap();
+        assert_eq!(copy.as_slice(), b"guard");
+        assert!(copy.canary_intact());
+    }
+}
@@ -0,0 +1,14 @@
+// src/locked/mod.rs
+//! Page-level secure allocations: locked, guarded, sealed and encrypted
+//! memory (Unix only)
+
+pub mod buffer;
+pub(crate) mod chacha20;
+pub mod enclave;
+pub(crate) mod guarded;
+pub(crate) mod region;
+pub mod sealed;
+
+pub use buffer::LockedBuffer;
+pub use enclave::{Enclave, EnclaveGuard};
+pub use sealed::SealedBuffer;
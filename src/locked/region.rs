@@ -0,0 +1,268 @@
+// src/locked/region.rs
+//! Page-aligned, `mlock`ed anonymous mappings.
+//!
+//! A [`LockedRegion`] is the backing store for locked [`Buffer`]s.  The
+//! mapping is excluded from core dumps where the platform supports it, and
+//! on drop the whole mapping is zeroized *before* it is unlocked and
+//! unmapped, so the plaintext can never reach swap after release.
+//!
+//! [`Buffer`]: crate::Buffer
+
+use crate::error::{BufferError, Result};
+use std::io;
+use std::ptr::NonNull;
+use std::sync::OnceLock;
+use zeroize::Zeroize;
+
+/// Returns the system page size.
+pub(crate) fn page_size() -> usize {
+    static PAGE_SIZE: OnceLock<usize> = OnceLock::new();
+    *PAGE_SIZE.get_or_init(|| {
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 { size as usize } else { 4096 }
+    })
+}
+
+/// Rounds `n` up to a whole number of pages (at least one).
+#[inline]
+pub(crate) fn round_to_pages(n: usize) -> usize {
+    let page = page_size();
+    n.max(1).div_ceil(page) * page
+}
+
+/// Current soft `RLIMIT_MEMLOCK`, or `u64::MAX` if unlimited or unknown.
+fn memlock_limit() -> u64 {
+    let mut lim = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut lim) } != 0
+        || lim.rlim_cur == libc::RLIM_INFINITY
+    {
+        return u64::MAX;
+    }
+    // `rlim_t` is not `u64` on every Unix.
+    #[allow(clippy::unnecessary_cast)]
+    let limit = lim.rlim_cur as u64;
+    limit
+}
+
+/// Excludes `[ptr, ptr + len)` from core dumps.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn exclude_from_dumps(ptr: *mut libc::c_void, len: usize) -> io::Result<()> {
+    if unsafe { libc::madvise(ptr, len, libc::MADV_DONTDUMP) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+unsafe fn exclude_from_dumps(ptr: *mut libc::c_void, len: usize) -> io::Result<()> {
+    if unsafe { libc::madvise(ptr, len, libc::MADV_NOCORE) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly"
+)))]
+unsafe fn exclude_from_dumps(_ptr: *mut libc::c_void, _len: usize) -> io::Result<()> {
+    Ok(())
+}
+
+/// An owned, locked anonymous mapping with `Vec`-like length tracking.
+///
+/// `capacity()` is the full page-rounded mapping; `len()` is the logical
+/// size exposed through [`as_slice`](Self::as_slice).
+pub(crate) struct LockedRegion {
+    ptr: NonNull<u8>,
+    map_len: usize,
+    len: usize,
+}
+
+// SAFETY: the region exclusively owns its mapping, like a `Vec<u8>`.
+unsafe impl Send for LockedRegion {}
+unsafe impl Sync for LockedRegion {}
+
+impl LockedRegion {
+    /// Maps and locks at least `capacity` bytes, with a logical length of 0.
+    ///
+    /// # Errors
+    ///
+    /// - [`BufferError::MemlockLimitExceeded`] if locking the pages would
+    ///   exceed `RLIMIT_MEMLOCK`, or the process may not lock memory at all.
+    /// - [`BufferError::Io`] if the mapping itself fails.
+    pub(crate) fn with_capacity(capacity: usize) -> Result<Self> {
+        let map_len = round_to_pages(capacity);
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                map_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANON,
+                -1,
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        if unsafe { libc::mlock(ptr, map_len) } != 0 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::munmap(ptr, map_len) };
+            return Err(match err.raw_os_error() {
+                Some(libc::ENOMEM) | Some(libc::EPERM) | Some(libc::EAGAIN) => {
+                    BufferError::MemlockLimitExceeded {
+                        requested: map_len,
+                        limit: memlock_limit(),
+                    }
+                }
+                _ => err.into(),
+            });
+        }
+
+        if let Err(err) = unsafe { exclude_from_dumps(ptr, map_len) } {
+            unsafe {
+                libc::munlock(ptr, map_len);
+                libc::munmap(ptr, map_len);
+            }
+            return Err(err.into());
+        }
+
+        Ok(Self {
+            // SAFETY: mmap never returns null on success.
+            ptr: unsafe { NonNull::new_unchecked(ptr.cast()) },
+            map_len,
+            len: 0,
+        })
+    }
+
+    /// Maps and locks a region of `len` zeroed bytes.
+    pub(crate) fn zeroed(len: usize) -> Result<Self> {
+        let mut region = Self::with_capacity(len)?;
+        // Anonymous mappings are zero-filled by the kernel.
+        region.len = len;
+        Ok(region)
+    }
+
+    /// Allocates a new locked region holding a copy of this one.
+    pub(crate) fn try_clone(&self) -> Result<Self> {
+        let mut copy = Self::with_capacity(self.map_len)?;
+        copy.resize(self.len);
+        copy.as_mut_slice().copy_from_slice(self.as_slice());
+        Ok(copy)
+    }
+
+    /// Size of the whole mapping.
+    #[inline]
+    pub(crate) fn capacity(&self) -> usize {
+        self.map_len
+    }
+
+    /// Sets the logical length, zero-filling any newly exposed bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` exceeds [`capacity`](Self::capacity).
+    pub(crate) fn resize(&mut self, new_len: usize) {
+        assert!(
+            new_len <= self.map_len,
+            "locked region resize to {} exceeds capacity {}",
+            new_len,
+            self.map_len
+        );
+        if new_len > self.len {
+            let extra = new_len - self.len;
+            self.spare_mut()[..extra].fill(0);
+        }
+        self.len = new_len;
+    }
+
+    /// Shortens the logical length; the discarded bytes are left in place.
+    #[inline]
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        self.len = self.len.min(new_len);
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// The mapped bytes past `len`.
+    #[inline]
+    pub(crate) fn spare_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            std::slice::from_raw_parts_mut(self.ptr.as_ptr().add(self.len), self.map_len - self.len)
+        }
+    }
+
+    /// Changes the protection of the whole mapping (`PROT_*` flags).
+    pub(crate) fn protect(&self, prot: libc::c_int) -> io::Result<()> {
+        if unsafe { libc::mprotect(self.ptr.as_ptr().cast(), self.map_len, prot) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Zeroizes the entire mapping and sets the length to 0.
+    pub(crate) fn wipe(&mut self) {
+        self.len = self.map_len;
+        self.as_mut_slice().zeroize();
+        self.len = 0;
+    }
+}
+
+impl Drop for LockedRegion {
+    fn drop(&mut self) {
+        // Wipe while the pages are still locked, then release them.
+        self.wipe();
+        let ptr = self.ptr.as_ptr().cast();
+        unsafe {
+            libc::munlock(ptr, self.map_len);
+            libc::munmap(ptr, self.map_len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_is_page_aligned_and_zeroed() {
+        let region = LockedRegion::zeroed(100).unwrap();
+        assert_eq!(region.as_slice(), &[0u8; 100][..]);
+        assert_eq!(region.capacity(), page_size());
+        assert_eq!(region.as_slice().as_ptr() as usize % page_size(), 0);
+    }
+
+    #[test]
+    fn test_resize_refills_with_zeros() {
+        let mut region = LockedRegion::zeroed(16).unwrap();
+        region.as_mut_slice().fill(0xEE);
+        region.truncate(4);
+        region.resize(16);
+        assert_eq!(&region.as_slice()[..4], &[0xEE; 4]);
+        assert_eq!(&region.as_slice()[4..], &[0u8; 12]);
+    }
+
+    #[test]
+    fn test_try_clone_copies_contents() {
+        let mut region = LockedRegion::zeroed(8).unwrap();
+        region.as_mut_slice().copy_from_slice(b"lockedup");
+        let copy = region.try_clone().unwrap();
+        assert_eq!(copy.as_slice(), b"lockedup");
+        assert_ne!(copy.as_slice().as_ptr(), region.as_slice().as_ptr());
+    }
+}
@@ -0,0 +1,271 @@
+// src/locked/sealed.rs
+//! [`SealedBuffer`]: locked memory that is inaccessible outside explicit
+//! access windows.
+
+use super::region::LockedRegion;
+use crate::buffer::Buffer;
+use crate::buffer::storage::Storage;
+use crate::error::{BufferError, Result};
+use std::sync::Mutex;
+
+/// A buffer whose pages are `PROT_NONE` except while a
+/// [`with_read`](Self::with_read) or [`with_write`](Self::with_write)
+/// closure is running.
+///
+/// Intended for long-lived secrets (host keys, master keys) that are needed
+/// rarely: a stray read through a dangling pointer or an out-of-bounds bug
+/// faults instead of leaking the secret.  The storage is also locked and
+/// excluded from core dumps, as for [`LockedBuffer`](crate::LockedBuffer).
+///
+/// Protection changes are reference-counted, so `with_read` may be called
+/// concurrently through a shared reference; the pages are resealed when the
+/// last reader leaves, including when a closure panics.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::SealedBuffer;
+/// # use secbuf::BufferError;
+///
+/// let mut key = SealedBuffer::new(64)?;
+/// key.with_write(|buf| buf.put_string(b"ed25519 secret"))??;
+///
+/// let len = key.with_read(|bytes| bytes.len())?;
+/// assert_eq!(len, 4 + 14);
+///
+/// // The full Buffer parsing API is available inside `with_write`.
+/// let secret = key.with_write(|buf| {
+///     buf.set_pos(0)?;
+///     buf.get_string()
+/// })??;
+/// assert_eq!(secret, b"ed25519 secret");
+/// # Ok::<(), BufferError>(())
+/// ```
+pub struct SealedBuffer {
+    inner: Buffer,
+    /// Number of active `with_read` windows.
+    readers: Mutex<usize>,
+}
+
+impl SealedBuffer {
+    /// Creates a sealed buffer with `size` writable bytes (initially empty,
+    /// like [`Buffer::new`]).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LockedBuffer::new`](crate::LockedBuffer::new).
+    pub fn new(size: usize) -> Result<Self> {
+        if size > crate::buffer::core::BUF_MAX_SIZE {
+            return Err(BufferError::SizeTooBig);
+        }
+        let mut inner = Buffer::from_storage(Storage::Locked(LockedRegion::zeroed(size)?));
+        inner.len = 0;
+        let sealed = Self {
+            inner,
+            readers: Mutex::new(0),
+        };
+        sealed.set_protection(libc::PROT_NONE)?;
+        Ok(sealed)
+    }
+
+    /// Creates a sealed buffer holding a copy of `data`.
+    ///
+    /// The caller remains responsible for wiping `data`.
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        let mut sealed = Self::new(data.len())?;
+        sealed.with_write(|buf| buf.put_bytes(data))??;
+        Ok(sealed)
+    }
+
+    /// Runs `f` with read access to the valid data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::Io`] if the protection cannot be changed; `f`
+    /// is not called in that case.
+    pub fn with_read<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Result<R> {
+        {
+            let mut readers = self.readers.lock().unwrap_or_else(|e| e.into_inner());
+            if *readers == 0 {
+                self.set_protection(libc::PROT_READ)?;
+            }
+            *readers += 1;
+        }
+        let _window = ReadWindow(self);
+        Ok(f(self.inner.as_slice()))
+    }
+
+    /// Runs `f` with read/write access to the underlying [`Buffer`].
+    ///
+    /// The buffer keeps its position and length between calls.  If `f`
+    /// grows the buffer or swaps it for another, the contents are moved back
+    /// into locked storage before resealing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::Io`] if the protection cannot be changed, or
+    /// an error from [`LockedBuffer::new`](crate::LockedBuffer::new) if the
+    /// contents had to be moved and no locked storage could be allocated (in
+    /// which case they are wiped).
+    pub fn with_write<R>(&mut self, f: impl FnOnce(&mut Buffer) -> R) -> Result<R> {
+        self.set_protection(libc::PROT_READ | libc::PROT_WRITE)?;
+        let result = {
+            let window = WriteWindow(self);
+            f(&mut window.0.inner)
+        };
+        // Dropping the window has resealed whatever storage `f` left behind.
+        if !self.inner.is_locked() {
+            self.relock()?;
+        }
+        Ok(result)
+    }
+
+    /// Valid data length.  Does not open the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the buffer holds no valid data.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Moves swapped-in heap contents back into sealed, locked storage.
+    #[cold]
+    fn relock(&mut self) -> Result<()> {
+        match LockedRegion::with_capacity(self.inner.data.len()) {
+            Ok(region) => {
+                self.inner.move_to_storage(Storage::Locked(region));
+                self.set_protection(libc::PROT_NONE)
+            }
+            Err(e) => {
+                self.inner.burn();
+                Err(e)
+            }
+        }
+    }
+
+    fn set_protection(&self, prot: libc::c_int) -> Result<()> {
+        if let Storage::Locked(region) = &self.inner.data {
+            region.protect(prot)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reseals on exit from the last concurrent `with_read`, even on panic.
+struct ReadWindow<'a>(&'a SealedBuffer);
+
+impl Drop for ReadWindow<'_> {
+    fn drop(&mut self) {
+        let mut readers = self.0.readers.lock().unwrap_or_else(|e| e.into_inner());
+        *readers -= 1;
+        if *readers == 0 {
+            let _ = self.0.set_protection(libc::PROT_NONE);
+        }
+    }
+}
+
+/// Reseals on exit from `with_write`, even on panic.
+struct WriteWindow<'a>(&'a mut SealedBuffer);
+
+impl Drop for WriteWindow<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.set_protection(libc::PROT_NONE);
+    }
+}
+
+impl Drop for SealedBuffer {
+    fn drop(&mut self) {
+        // The inner buffer wipes its storage on drop, which needs write
+        // access.  Failure here would fault on the wipe, so abort instead.
+        if self.set_protection(libc::PROT_READ | libc::PROT_WRITE).is_err() {
+            std::process::abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_write_then_read() {
+        let mut sealed = SealedBuffer::new(32).unwrap();
+        sealed
+            .with_write(|buf| {
+                buf.put_u32(7)?;
+                buf.put_bytes(b"key")
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(sealed.len(), 7);
+        let copy = sealed.with_read(|bytes| bytes.to_vec()).unwrap();
+        assert_eq!(copy, [0, 0, 0, 7, b'k', b'e', b'y']);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let sealed = SealedBuffer::from_slice(b"host key").unwrap();
+        assert!(sealed.with_read(|b| b == b"host key").unwrap());
+    }
+
+    #[test]
+    fn test_growth_and_swap_stay_sealed() {
+        let mut sealed = SealedBuffer::new(4).unwrap();
+        sealed
+            .with_write(|buf| {
+                buf.set_growth_policy(crate::GrowthPolicy::Doubling { max: 8192 })
+                    .unwrap();
+                buf.put_bytes(&[9; 5000]).unwrap();
+            })
+            .unwrap();
+        assert!(sealed.inner.is_locked());
+        assert_eq!(sealed.len(), 5000);
+
+        sealed
+            .with_write(|buf| {
+                let mut heap = Buffer::from_vec(b"swapped".to_vec());
+                std::mem::swap(buf, &mut heap);
+            })
+            .unwrap();
+        assert!(sealed.inner.is_locked());
+        assert!(sealed.with_read(|b| b == b"swapped").unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_readers() {
+        let sealed = Arc::new(SealedBuffer::from_slice(&[0x5A; 256]).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let sealed = Arc::clone(&sealed);
+                std::thread::spawn(move || {
+                    for _ in 0..200 {
+                        let sum: u32 = sealed
+                            .with_read(|b| b.iter().map(|&x| x as u32).sum())
+                            .unwrap();
+                        assert_eq!(sum, 0x5A * 256);
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(*sealed.readers.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reseals_after_panic() {
+        let sealed = SealedBuffer::from_slice(b"x").unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            sealed.with_read(|_| panic!("boom")).unwrap();
+        }));
+        assert!(result.is_err());
+        assert_eq!(*sealed.readers.lock().unwrap(), 0);
+        assert!(sealed.with_read(|b| b == b"x").unwrap());
+    }
+}
@@ -1,7 +1,14 @@
 // src/pool/config.rs
 //! Configuration for buffer pools
 
+use crate::buffer::{AllocMode, Buffer};
+
 /// Configuration for buffer pool behavior.
+///
+/// Start from [`PoolConfig::default()`] or a preset and override fields with
+/// `..`, so that fields added in later releases do not break the literal.
+/// Literals written before `alloc_mode` existed must add
+/// `..PoolConfig::default()` (or set `alloc_mode`) to compile.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
     /// Size of each buffer in the pool (bytes)
@@ -10,6 +17,8 @@ pub struct PoolConfig {
     pub max_pool_size: usize,
     /// Number of buffers to pre-allocate at startup
     pub min_pool_size: usize,
+    /// How each pooled buffer is allocated (ignored by `LockedBufferPool`)
+    pub alloc_mode: AllocMode,
 }
 
 impl Default for PoolConfig {
@@ -18,17 +27,48 @@ impl Default for PoolConfig {
             buffer_size: 8192,  // 8KB buffers
             max_pool_size: 100, // Keep up to 100 buffers
             min_pool_size: 10,  // Pre-warm with 10
+            alloc_mode: AllocMode::Heap,
         }
     }
 }
 
 impl PoolConfig {
+    /// Allocates one buffer according to this configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a guarded mapping cannot be created, as a heap allocation
+    /// failure would abort.
+    pub(crate) fn allocate(&self) -> Buffer {
+        Buffer::with_alloc_mode(self.buffer_size, self.alloc_mode)
+            .unwrap_or_else(|e| panic!("pool buffer allocation failed: {}", e))
+    }
+
+    /// Returns this configuration with buffers allocated as `mode`.
+    pub fn with_alloc_mode(mut self, mode: AllocMode) -> Self {
+        self.alloc_mode = mode;
+        self
+    }
+
+    /// Returns `true` if `buf` has the storage kind and capacity that
+    /// [`allocate`](Self::allocate) produces.
+    ///
+    /// Pools check this before reusing a buffer: the fast pool's thread
+    /// cache is shared by every pool on a thread, and a caller may have
+    /// grown or replaced a pooled buffer.
+    pub(crate) fn matches(&self, buf: &Buffer) -> bool {
+        buf.is_guarded() == (self.alloc_mode == AllocMode::Guarded)
+            && !buf.is_locked()
+            && buf.capacity() == self.buffer_size
+    }
+
     /// Configuration for embedded systems (low memory).
     pub fn small() -> Self {
         Self {
             buffer_size: 1024,
             max_pool_size: 20,
             min_pool_size: 5,
+            alloc_mode: AllocMode::Heap,
         }
     }
 
@@ -38,6 +78,7 @@ impl PoolConfig {
             buffer_size: 65536, // 64KB buffers
             max_pool_size: 1000,
             min_pool_size: 50,
+            alloc_mode: AllocMode::Heap,
         }
     }
 
@@ -47,6 +88,7 @@ impl PoolConfig {
             buffer_size: 1500, // Standard MTU
             max_pool_size: 500,
             min_pool_size: 20,
+            alloc_mode: AllocMode::Heap,
         }
     }
 }
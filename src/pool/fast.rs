@@ -11,7 +11,9 @@
 //!
 //! # Thread-Local Cache Behaviour
 //!
-//! Each thread maintains a private cache of up to 16 buffers.  On thread exit
+//! Each thread maintains a private cache of up to 16 buffers, shared by every
+//! pool used on that thread.  A pool only takes buffers from it whose storage
+//! kind and capacity match its own [`PoolConfig`].  On thread exit
 //! the `Vec<Buffer>` drops naturally: each `Buffer` is securely zeroed by its
 //! `#[zeroize(drop)]` destructor and then freed.  Buffers are **not** returned
 //! to the global pool on thread exit — they are simply freed.  This is safe and
@@ -48,6 +50,7 @@
 use super::config::PoolConfig;
 use super::stats::FastPoolStats;
 use crate::buffer::Buffer;
+use crate::buffer::frozen::Owner;
 use std::cell::RefCell;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -168,7 +171,7 @@ impl FastBufferPool {
     pub fn new(config: PoolConfig) -> Self {
         let queue = Arc::new(LockFreeQueue::new());
         for _ in 0..config.min_pool_size {
-            queue.push(Buffer::new(config.buffer_size));
+            queue.push(config.allocate());
         }
         Self {
             global_pool: queue,
@@ -193,15 +196,14 @@ impl FastBufferPool {
     pub fn acquire(&self) -> FastPooledBuffer {
         self.stats.acquired.fetch_add(1, Ordering::Relaxed);
 
-        // Tier 1: thread-local cache (no synchronisation).
+        // Tier 1: thread-local cache (no synchronisation).  The cache is
+        // shared by every pool on the thread, so only take a buffer this
+        // pool's configuration would have allocated.
         let buffer = THREAD_CACHE.with(|cache| {
             let mut c = cache.borrow_mut();
-            if let Some(buf) = c.pop() {
-                self.stats.cache_hits.fetch_add(1, Ordering::Relaxed);
-                Some(buf)
-            } else {
-                None
-            }
+            let index = c.iter().rposition(|buf| self.config.matches(buf))?;
+            self.stats.cache_hits.fetch_add(1, Ordering::Relaxed);
+            Some(c.swap_remove(index))
         });
 
         if let Some(buf) = buffer {
@@ -226,7 +228,7 @@ impl FastBufferPool {
         // Tier 3: fresh allocation.
         self.stats.allocated.fetch_add(1, Ordering::Relaxed);
         FastPooledBuffer {
-            buffer: Some(Buffer::new(self.config.buffer_size)),
+            buffer: Some(self.config.allocate()),
             pool: Arc::clone(&self.global_pool),
             config: self.config.clone(),
             stats: Arc::clone(&self.stats),
@@ -278,20 +280,24 @@ impl FastBufferPool {
         // bounded and not a safety concern.
         let current = self.global_pool.len();
         for _ in current..target {
-            self.global_pool.push(Buffer::new(self.config.buffer_size));
+            self.global_pool.push(self.config.allocate());
         }
     }
 
-    /// Pushes all buffers from the calling thread's local cache back to the
-    /// global pool (where space permits) or drops them.
+    /// Pushes this pool's buffers from the calling thread's local cache back
+    /// to the global pool (where space permits) or drops them.
     ///
+    /// Buffers cached by other pools on the same thread are left in place.
     /// Call this before a long-lived thread terminates to reclaim cached buffers
     /// without waiting for thread-exit destruction.
     pub fn clear_thread_cache(&self) {
         THREAD_CACHE.with(|cache| {
             let mut c = cache.borrow_mut();
-            while let Some(mut buf) = c.pop() {
-                if self.global_pool.len() < self.config.max_pool_size {
+            let cached = std::mem::take(&mut *c);
+            for mut buf in cached {
+                if !self.config.matches(&buf) {
+                    c.push(buf);
+                } else if self.global_pool.len() < self.config.max_pool_size {
                     // buf is already burned by the return path; push as-is.
                     self.global_pool.push(buf);
                 } else {
@@ -328,6 +334,15 @@ impl FastPooledBuffer {
         self.buffer.take().unwrap()
     }
 
+    /// Converts the buffer into a read-only, shared
+    /// [`FrozenBuffer`](crate::FrozenBuffer).
+    ///
+    /// The buffer is burned and returned to the pool when the last view
+    /// drops, instead of when this wrapper does.
+    pub fn freeze(self) -> crate::FrozenBuffer {
+        crate::FrozenBuffer::from_owner(Owner::FastPooled(self))
+    }
+
     /// Immediately burns and drops the buffer without returning it to the pool.
     pub fn drop_now(mut self) {
         if let Some(mut buffer) = self.buffer.take() {
@@ -371,6 +386,12 @@ impl Drop for FastPooledBuffer {
             buffer.burn();
             self.stats.returned.fetch_add(1, Ordering::Relaxed);
 
+            // A buffer that was grown or replaced is not what the pool
+            // promises to hand out; it is already burned and freed here.
+            if !self.config.matches(&buffer) {
+                return;
+            }
+
             // Wrap in Option so the closure can conditionally take ownership
             // without unconditionally moving `buffer` — which would make the
             // fallback `self.pool.push(buffer)` below a use-after-move error.
@@ -415,6 +436,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         let _buf = pool.acquire();
         assert_eq!(pool.stats().acquired, 1);
@@ -426,6 +448,7 @@ mod tests {
             buffer_size: 64,
             max_pool_size: 4,
             min_pool_size: 0,
+            ..PoolConfig::default()
         });
 
         {
@@ -447,6 +470,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 100,
             min_pool_size: 10,
+            ..PoolConfig::default()
         });
         { let _buf = pool.acquire(); }
         assert_eq!(pool.stats().returned, 1);
@@ -458,6 +482,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         let mut buf = pool.acquire();
         buf.put_u32(12345).unwrap();
@@ -471,6 +496,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         let pooled = pool.acquire();
         let _owned = pooled.leak();
@@ -483,6 +509,7 @@ mod tests {
             buffer_size: 512,
             max_pool_size: 20,
             min_pool_size: 0,
+            ..PoolConfig::default()
         });
         pool.warm(10);
         assert!(pool.available() <= 10);
@@ -494,6 +521,7 @@ mod tests {
             buffer_size: 128,
             max_pool_size: 20,
             min_pool_size: 0,
+            ..PoolConfig::default()
         });
         // Acquire and release several buffers (go to thread cache).
         for _ in 0..5 { let _b = pool.acquire(); }
@@ -502,6 +530,41 @@ mod tests {
         assert!(pool.available() > 0);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_pools_do_not_share_cached_buffers() {
+        let heap = FastBufferPool::new(PoolConfig {
+            buffer_size: 64,
+            max_pool_size: 4,
+            min_pool_size: 0,
+            ..PoolConfig::default()
+        });
+        let guarded = FastBufferPool::new(PoolConfig {
+            buffer_size: 64,
+            max_pool_size: 4,
+            min_pool_size: 0,
+            alloc_mode: crate::AllocMode::Guarded,
+        });
+
+        // Both returns land in the same thread-local cache.
+        drop(heap.acquire());
+        drop(guarded.acquire());
+        let buf = guarded.acquire();
+        assert!(buf.is_guarded());
+        let plain = heap.acquire();
+        assert!(!plain.is_guarded());
+        assert_eq!(heap.stats().cache_hits, 1);
+        assert_eq!(guarded.stats().cache_hits, 1);
+        drop((buf, plain));
+
+        // Clearing one pool's share leaves the other's in the cache.
+        guarded.clear_thread_cache();
+        assert_eq!(guarded.available(), 1);
+        assert_eq!(heap.available(), 0);
+        assert!(!heap.acquire().is_guarded());
+        assert_eq!(heap.stats().cache_hits, 2);
+    }
+
     #[test]
     fn test_multi_thread() {
         use std::sync::Arc;
@@ -511,6 +574,7 @@ mod tests {
             buffer_size: 256,
             max_pool_size: 64,
             min_pool_size: 4,
+            ..PoolConfig::default()
         }));
 
         let handles: Vec<_> = (0..4)
@@ -0,0 +1,282 @@
+// src/pool/locked.rs
+//! Mutex-based pool of [`LockedBuffer`]s (Unix only).
+//!
+//! Locking and unlocking pages costs two syscalls and counts against
+//! `RLIMIT_MEMLOCK`, so keeping a warm set of locked buffers is much cheaper
+//! than creating them per use.  Buffers are burned before they re-enter the
+//! pool, exactly as in [`BufferPool`](super::BufferPool).
+
+use super::config::PoolConfig;
+use super::stats::PoolStats;
+use crate::buffer::Buffer;
+use crate::buffer::frozen::Owner;
+use crate::error::Result;
+use crate::locked::LockedBuffer;
+use crate::locked::region::round_to_pages;
+use std::sync::{Arc, Mutex};
+
+pub(crate) struct LockedPoolInner {
+    pub(crate) buffers: Vec<LockedBuffer>,
+    pub(crate) config: PoolConfig,
+    pub(crate) total_allocated: usize,
+    pub(crate) total_acquired: usize,
+    pub(crate) total_returned: usize,
+}
+
+/// Thread-safe pool of [`LockedBuffer`]s, configured with [`PoolConfig`].
+///
+/// Unlike [`BufferPool`](super::BufferPool), creating the pool and acquiring
+/// from it are fallible: locking more pages may exceed `RLIMIT_MEMLOCK`.
+///
+/// # Example
+///
+/// ```rust
+/// use secbuf::prelude::*;
+///
+/// let pool = LockedBufferPool::new(PoolConfig {
+///     buffer_size: 256,
+///     max_pool_size: 4,
+///     min_pool_size: 1,
+///     ..PoolConfig::default()
+/// })?;
+///
+/// let mut key = pool.acquire()?;
+/// key.put_bytes(&[0x42; 32])?;
+/// assert!(key.is_locked());
+/// // Burned (zeroed) then returned to the pool on drop.
+/// # Ok::<(), secbuf::BufferError>(())
+/// ```
+pub struct LockedBufferPool {
+    pub(crate) inner: Arc<Mutex<LockedPoolInner>>,
+}
+
+impl LockedBufferPool {
+    /// Creates a pool and pre-warms it with `config.min_pool_size` locked
+    /// buffers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`LockedBuffer::new`], typically
+    /// [`BufferError::MemlockLimitExceeded`](crate::BufferError::MemlockLimitExceeded).
+    pub fn new(config: PoolConfig) -> Result<Self> {
+        let mut buffers = Vec::with_capacity(config.min_pool_size);
+        for _ in 0..config.min_pool_size {
+            buffers.push(LockedBuffer::new(config.buffer_size)?);
+        }
+        Ok(Self {
+            inner: Arc::new(Mutex::new(LockedPoolInner {
+                buffers,
+                config,
+                total_allocated: 0,
+                total_acquired: 0,
+                total_returned: 0,
+            })),
+        })
+    }
+
+    /// Acquires a locked buffer, allocating a fresh one if the pool is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if a fresh buffer is needed and cannot be
+    /// locked.
+    pub fn acquire(&self) -> Result<PooledLockedBuffer> {
+        let mut inner = self.inner.lock().unwrap();
+        let buffer = match inner.buffers.pop() {
+            Some(buffer) => buffer,
+            None => {
+                let buffer = LockedBuffer::new(inner.config.buffer_size)?;
+                inner.total_allocated += 1;
+                buffer
+            }
+        };
+        inner.total_acquired += 1;
+
+        Ok(PooledLockedBuffer {
+            buffer: Some(buffer),
+            pool: Arc::clone(&self.inner),
+        })
+    }
+
+    /// Number of buffers currently idle in the pool.
+    pub fn available(&self) -> usize {
+        self.inner.lock().unwrap().buffers.len()
+    }
+
+    /// Returns a snapshot of pool statistics.
+    pub fn stats(&self) -> PoolStats {
+        let inner = self.inner.lock().unwrap();
+        PoolStats {
+            available: inner.buffers.len(),
+            total_allocated: inner.total_allocated,
+            total_acquired: inner.total_acquired,
+            total_returned: inner.total_returned,
+            buffer_size: inner.config.buffer_size,
+            max_pool_size: inner.config.max_pool_size,
+        }
+    }
+
+    /// Truncates idle buffers to `min_pool_size`, unlocking the excess.
+    pub fn shrink(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        let min_size = inner.config.min_pool_size;
+        inner.buffers.truncate(min_size);
+        inner.buffers.shrink_to_fit();
+    }
+
+    /// Removes all idle buffers from the pool.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.buffers.clear();
+    }
+}
+
+/// A locked buffer borrowed from a [`LockedBufferPool`].
+///
+/// On drop the buffer is burned and returned to the pool if space permits;
+/// otherwise it is dropped, which wipes and unlocks its pages.
+pub struct PooledLockedBuffer {
+    pub(crate) buffer: Option<LockedBuffer>,
+    pub(crate) pool: Arc<Mutex<LockedPoolInner>>,
+}
+
+impl PooledLockedBuffer {
+    /// Extracts the buffer from the pool wrapper without returning it.
+    pub fn leak(mut self) -> LockedBuffer {
+        self.buffer.take().unwrap()
+    }
+
+    /// Converts the buffer into a read-only, shared
+    /// [`FrozenBuffer`](crate::FrozenBuffer).
+    ///
+    /// The buffer is burned and returned to the pool when the last view
+    /// drops, instead of when this wrapper does.
+    pub fn freeze(self) -> crate::FrozenBuffer {
+        crate::FrozenBuffer::from_owner(Owner::Locked(self))
+    }
+
+    /// Capacity of the underlying buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.as_ref().unwrap().capacity()
+    }
+}
+
+impl std::ops::Deref for PooledLockedBuffer {
+    type Target = Buffer;
+    fn deref(&self) -> &Self::Target {
+        self.buffer.as_ref().unwrap()
+    }
+}
+
+impl std::ops::DerefMut for PooledLockedBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.buffer.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledLockedBuffer {
+    fn drop(&mut self) {
+        if let Some(mut buffer) = self.buffer.take() {
+            buffer.burn();
+
+            let mut inner = self.pool.lock().unwrap();
+            inner.total_returned += 1;
+
+            // A buffer whose storage was swapped out for heap memory, or that
+            // grew past its page-rounded size, is not fit to hand out again.
+            let fits = buffer.is_locked()
+                && buffer.capacity() == round_to_pages(inner.config.buffer_size);
+            if fits && inner.buffers.len() < inner.config.max_pool_size {
+                inner.buffers.push(buffer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(min: usize, max: usize) -> PoolConfig {
+        PoolConfig {
+            buffer_size: 64,
+            max_pool_size: max,
+            min_pool_size: min,
+            ..PoolConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_locked_pool_reuses_burned_buffers() {
+        let pool = LockedBufferPool::new(config(1, 2)).unwrap();
+        assert_eq!(pool.available(), 1);
+
+        let ptr = {
+            let mut buf = pool.acquire().unwrap();
+            buf.put_bytes(&[0xFF; 16]).unwrap();
+            buf.as_slice().as_ptr()
+        };
+        assert_eq!(pool.available(), 1);
+
+        let mut buf = pool.acquire().unwrap();
+        assert!(buf.is_locked());
+        assert_eq!(buf.as_slice().as_ptr(), ptr);
+        buf.set_len(16).unwrap();
+        assert_eq!(buf.as_slice(), &[0u8; 16]);
+    }
+
+    #[test]
+    fn test_locked_pool_respects_max() {
+        let pool = LockedBufferPool::new(config(0, 1)).unwrap();
+        let a = pool.acquire().unwrap();
+        let b = pool.acquire().unwrap();
+        drop(a);
+        drop(b);
+        let stats = pool.stats();
+        assert_eq!(stats.available, 1);
+        assert_eq!(stats.total_allocated, 2);
+        assert_eq!(stats.total_returned, 2);
+    }
+
+    #[test]
+    fn test_swapped_storage_is_not_pooled() {
+        let pool = LockedBufferPool::new(config(0, 4)).unwrap();
+        {
+            let mut buf = pool.acquire().unwrap();
+            let mut plain = Buffer::new(64);
+            std::mem::swap(&mut *buf, &mut plain);
+            assert!(plain.is_locked());
+        }
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test]
+    fn test_grown_buffer_is_not_pooled() {
+        let pool = LockedBufferPool::new(config(0, 4)).unwrap();
+        {
+            let mut buf = pool.acquire().unwrap();
+            buf.reserve(round_to_pages(64) + 1);
+            assert!(buf.is_locked());
+            assert!(buf.capacity() > round_to_pages(64));
+        }
+        assert_eq!(pool.available(), 0);
+        assert_eq!(pool.stats().total_returned, 1);
+    }
+
+    #[test]
+    fn test_frozen_locked_buffer_returns_to_pool() {
+        let pool = LockedBufferPool::new(config(0, 4)).unwrap();
+        let mut buf = pool.acquire().unwrap();
+        buf.put_bytes(b"host key").unwrap();
+
+        let frozen = buf.freeze();
+        let view = frozen.slice(5..).unwrap();
+        drop(frozen);
+        assert_eq!(&view[..], b"key");
+        assert_eq!(pool.available(), 0);
+
+        drop(view);
+        assert_eq!(pool.available(), 1);
+    }
+}
@@ -3,10 +3,14 @@
 
 pub(crate) mod config;
 pub(crate) mod fast;
+#[cfg(unix)]
+pub(crate) mod locked;
 pub(crate) mod standard;
 pub(crate) mod stats;
 
 pub use config::PoolConfig;
 pub use fast::{FastBufferPool, FastPooledBuffer};
+#[cfg(unix)]
+pub use locked::{LockedBufferPool, PooledLockedBuffer};
 pub use standard::{BufferPool, PooledBuffer};
 pub use stats::{FastPoolStats, PoolStats};
@@ -27,6 +27,7 @@
 use super::config::PoolConfig;
 use super::stats::PoolStats;
 use crate::buffer::Buffer;
+use crate::buffer::frozen::Owner;
 use std::sync::{Arc, Mutex};
 
 pub(crate) struct PoolInner {
@@ -56,6 +57,7 @@ pub(crate) struct PoolInner {
 ///     buffer_size: 4096,
 ///     max_pool_size: 100,
 ///     min_pool_size: 10,
+///     ..PoolConfig::default()
 /// });
 ///
 /// let mut buf = pool.acquire();
@@ -78,7 +80,7 @@ impl BufferPool {
     pub fn new(config: PoolConfig) -> Self {
         let mut buffers = Vec::with_capacity(config.min_pool_size);
         for _ in 0..config.min_pool_size {
-            buffers.push(Buffer::new(config.buffer_size));
+            buffers.push(config.allocate());
         }
         Self {
             inner: Arc::new(Mutex::new(PoolInner {
@@ -101,7 +103,7 @@ impl BufferPool {
 
         let buffer = inner.buffers.pop().unwrap_or_else(|| {
             inner.total_allocated += 1;
-            Buffer::new(inner.config.buffer_size)
+            inner.config.allocate()
         });
 
         PooledBuffer {
@@ -150,10 +152,10 @@ impl BufferPool {
     pub fn grow(&self, target_size: usize) {
         let mut inner = self.inner.lock().unwrap();
         let max_size = inner.config.max_pool_size;
-        let buffer_size = inner.config.buffer_size;
         let target = target_size.min(max_size);
         while inner.buffers.len() < target {
-            inner.buffers.push(Buffer::new(buffer_size));
+            let buffer = inner.config.allocate();
+            inner.buffers.push(buffer);
         }
     }
 }
@@ -180,6 +182,15 @@ impl PooledBuffer {
         self.buffer.take().unwrap()
     }
 
+    /// Converts the buffer into a read-only, shared
+    /// [`FrozenBuffer`](crate::FrozenBuffer).
+    ///
+    /// The buffer is burned and returned to the pool when the last view
+    /// drops, instead of when this wrapper does.
+    pub fn freeze(self) -> crate::FrozenBuffer {
+        crate::FrozenBuffer::from_owner(Owner::Pooled(self))
+    }
+
     /// Immediately and securely drops the buffer, bypassing pool return.
     pub fn drop_now(mut self) {
         if let Some(mut buffer) = self.buffer.take() {
@@ -227,7 +238,9 @@ impl Drop for PooledBuffer {
             let mut inner = self.pool.lock().unwrap();
             inner.total_returned += 1;
 
-            if inner.buffers.len() < inner.config.max_pool_size {
+            // A buffer that was grown or replaced is not what the pool
+            // promises to hand out.
+            if inner.config.matches(&buffer) && inner.buffers.len() < inner.config.max_pool_size {
                 inner.buffers.push(buffer);
             }
             // else: buffer is already burned; the Vec<u8> will be freed here.
@@ -245,6 +258,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         assert_eq!(pool.available(), 2);
         let _buf = pool.acquire();
@@ -258,6 +272,7 @@ mod tests {
             buffer_size: 64,
             max_pool_size: 2,
             min_pool_size: 0,
+            ..PoolConfig::default()
         });
 
         {
@@ -278,6 +293,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         let initial_available = pool.available();
         {
@@ -294,6 +310,7 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         let pooled = pool.acquire();
         let _owned = pooled.leak();
@@ -306,18 +323,59 @@ mod tests {
             buffer_size: 1024,
             max_pool_size: 10,
             min_pool_size: 2,
+            ..PoolConfig::default()
         });
         let initial = pool.available();
         { let _buf = pool.acquire(); } // normal drop
         assert_eq!(pool.available(), initial);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_guarded_pool() {
+        let pool = BufferPool::new(PoolConfig {
+            buffer_size: 64,
+            max_pool_size: 2,
+            min_pool_size: 1,
+            alloc_mode: crate::AllocMode::Guarded,
+        });
+        let mut buf = pool.acquire();
+        assert!(buf.is_guarded());
+        buf.put_bytes(&[0xAA; 64]).unwrap();
+        drop(buf);
+        pool.grow(2);
+        assert_eq!(pool.available(), 2);
+        assert!(pool.acquire().is_guarded());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replaced_buffer_is_not_repooled() {
+        let pool = BufferPool::new(PoolConfig {
+            buffer_size: 64,
+            max_pool_size: 4,
+            min_pool_size: 0,
+            alloc_mode: crate::AllocMode::Guarded,
+        });
+        let mut buf = pool.acquire();
+        *buf = Buffer::new(64);
+        drop(buf);
+        assert_eq!(pool.available(), 0);
+
+        let mut buf = pool.acquire();
+        buf.resize(128).unwrap();
+        drop(buf);
+        assert_eq!(pool.available(), 0);
+        assert_eq!(pool.stats().total_returned, 2);
+    }
+
     #[test]
     fn test_grow_shrink() {
         let pool = BufferPool::new(PoolConfig {
             buffer_size: 64,
             max_pool_size: 20,
             min_pool_size: 0,
+            ..PoolConfig::default()
         });
         pool.grow(10);
         assert_eq!(pool.available(), 10);
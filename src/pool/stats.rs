@@ -174,6 +174,7 @@ impl FastPoolStats {
     ///     buffer_size: 8192,
     ///     max_pool_size: 100,
     ///     min_pool_size: 10,
+    ///     ..PoolConfig::default()
     /// });
     ///
     /// let stats = pool.stats();
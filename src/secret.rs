@@ -0,0 +1,191 @@
+// src/secret.rs
+//! Owned secret byte strings that are wiped on drop.
+//!
+//! [`SecretBytes`] is what the `*_secret` getters on [`Buffer`](crate::Buffer)
+//! hand back, so that pulling a key or password out of a buffer does not
+//! leave an unprotected heap copy behind.
+//!
+//! # Design
+//!
+//! - The whole allocation (including spare capacity) is zeroized on drop.
+//! - There is no `Clone` impl unless the `secret-clone` feature is enabled,
+//!   so every copy of a secret is an explicit decision.
+//! - `Debug` prints only the length, never the contents.
+//! - Access goes through [`expose_secret`](SecretBytes::expose_secret) to
+//!   make reads easy to audit.
+
+use crate::error::{BufferError, Result};
+use alloc::vec::Vec;
+use core::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A heap-allocated byte string that is securely zeroed on drop.
+///
+/// # Examples
+///
+/// ```
+/// use secbuf::prelude::*;
+///
+/// let mut buf = Buffer::new(64);
+/// buf.put_string(b"hunter2")?;
+/// buf.set_pos(0)?;
+///
+/// let password = buf.get_string_secret()?;
+/// assert_eq!(password.expose_secret(), b"hunter2");
+/// assert_eq!(format!("{password:?}"), "SecretBytes([REDACTED; 7 bytes])");
+/// # Ok::<(), BufferError>(())
+/// ```
+pub struct SecretBytes {
+    bytes: Vec<u8>,
+}
+
+impl SecretBytes {
+    /// Takes ownership of `bytes`; the allocation is wiped when this drops.
+    #[inline]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// Copies `bytes` into a new secret.
+    #[inline]
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        Self::new(bytes.to_vec())
+    }
+
+    /// Returns the secret contents.
+    #[inline]
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the secret contents mutably.
+    #[inline]
+    pub fn expose_secret_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    /// Length of the secret in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// `true` if the secret is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Compares the secret with `other` in constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use secbuf::SecretBytes;
+    ///
+    /// let token = SecretBytes::from_slice(b"bearer-token");
+    /// assert!(token.ct_eq(b"bearer-token"));
+    /// assert!(!token.ct_eq(b"bearer-tokem"));
+    /// ```
+    #[inline]
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        crate::ct::ct_eq(&self.bytes, other)
+    }
+
+    /// Replaces the contents with `other` if `choice` is `true`, without
+    /// branching on `choice`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidState`] if `other.len()` differs from
+    /// the secret's length.
+    #[inline]
+    pub fn ct_select(&mut self, other: &[u8], choice: bool) -> Result<()> {
+        if other.len() != self.bytes.len() {
+            return Err(BufferError::InvalidState("ct_select length mismatch"));
+        }
+        crate::ct::ct_select(&mut self.bytes, other, choice);
+        Ok(())
+    }
+
+    /// Returns `true` if every byte is zero, in constant time.
+    #[inline]
+    pub fn ct_is_zero(&self) -> bool {
+        crate::ct::ct_is_zero(&self.bytes)
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {} bytes])", self.bytes.len())
+    }
+}
+
+impl Zeroize for SecretBytes {
+    /// Wipes the full allocation and truncates to zero length.
+    fn zeroize(&mut self) {
+        // Vec::zeroize also clears spare capacity, which may still hold
+        // bytes that were truncated away before this value was built.
+        self.bytes.zeroize();
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretBytes {}
+
+#[cfg(feature = "secret-clone")]
+impl Clone for SecretBytes {
+    fn clone(&self) -> Self {
+        Self::from_slice(&self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = SecretBytes::from_slice(b"top secret");
+        let dbg = format!("{secret:?}");
+        assert!(!dbg.contains("top"));
+        assert!(dbg.contains("10 bytes"));
+    }
+
+    #[test]
+    fn test_zeroize_clears_contents() {
+        let mut secret = SecretBytes::new(vec![0xAA; 32]);
+        secret.zeroize();
+        assert!(secret.is_empty());
+        assert!(secret.bytes.capacity() >= 32);
+        // Spare capacity must have been wiped, not just the length reset.
+        let spare = secret.bytes.spare_capacity_mut();
+        assert!(spare.iter().all(|b| unsafe { b.assume_init() } == 0));
+    }
+
+    #[test]
+    fn test_ct_helpers() {
+        let mut secret = SecretBytes::from_slice(&[0; 4]);
+        assert!(secret.ct_is_zero());
+        assert!(secret.ct_eq(&[0; 4]));
+        secret.ct_select(&[1, 2, 3, 4], true).unwrap();
+        assert!(secret.ct_eq(&[1, 2, 3, 4]));
+        assert!(!secret.ct_is_zero());
+        assert_eq!(
+            secret.ct_select(&[0; 3], true),
+            Err(BufferError::InvalidState("ct_select length mismatch"))
+        );
+        assert!(secret.ct_eq(&[1, 2, 3, 4]));
+    }
+}
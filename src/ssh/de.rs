@@ -0,0 +1,326 @@
+// src/ssh/de.rs
+//! SSH binary [`Deserializer`](serde::Deserializer) over a [`Buffer`] or a
+//! borrowed byte slice
+
+use crate::buffer::ops::MAX_STRING_LEN;
+use crate::buffer::{Buffer, BufferRead};
+use crate::error::{BufferError, Result};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+/// An SSH string read from the input.
+pub(super) enum Reference<'de, 'a> {
+    /// Borrowed from input that outlives the deserializer.
+    Borrowed(&'de [u8]),
+    /// Borrowed from the input only until the next read.
+    Copied(&'a [u8]),
+}
+
+/// Input a [`Deserializer`] can read from.
+pub(super) trait Input<'de>: BufferRead {
+    /// Reads an SSH string, checking it against [`MAX_STRING_LEN`].
+    fn get_string_ref(&mut self) -> Result<Reference<'de, '_>>;
+}
+
+impl<'de> Input<'de> for Buffer {
+    #[inline]
+    fn get_string_ref(&mut self) -> Result<Reference<'de, '_>> {
+        Buffer::get_string_ref(self).map(Reference::Copied)
+    }
+}
+
+impl<'de> Input<'de> for &'de [u8] {
+    fn get_string_ref(&mut self) -> Result<Reference<'de, '_>> {
+        let len = self.get_u32()? as usize;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidString);
+        }
+        if len > self.len() {
+            return Err(BufferError::overflow("get_string_ref", 0, len, self.len()));
+        }
+        let (string, rest) = self.split_at(len);
+        *self = rest;
+        Ok(Reference::Borrowed(string))
+    }
+}
+
+/// Reads serde values in the SSH encoding.
+pub(super) struct Deserializer<'a, I> {
+    input: &'a mut I,
+}
+
+impl<'a, I> Deserializer<'a, I> {
+    pub(super) fn new(input: &'a mut I) -> Self {
+        Self { input }
+    }
+}
+
+impl<'de, I: Input<'de>> Deserializer<'_, I> {
+    /// Reads a sequence or map count, rejecting counts over
+    /// [`MAX_STRING_LEN`] or that cannot fit in the remaining input.
+    fn count(&mut self) -> Result<usize> {
+        let count = self.input.get_u32()? as usize;
+        if count > MAX_STRING_LEN {
+            return Err(BufferError::InvalidData("sequence count exceeds the SSH string limit"));
+        }
+        // Every supported element takes at least one byte, except `()`.
+        if count > self.input.readable() {
+            return Err(BufferError::InvalidData("sequence count exceeds remaining data"));
+        }
+        Ok(count)
+    }
+}
+
+#[cold]
+fn out_of_range() -> BufferError {
+    BufferError::InvalidData("integer out of range")
+}
+
+#[inline]
+fn utf8(bytes: &[u8]) -> Result<&str> {
+    core::str::from_utf8(bytes).map_err(|_| BufferError::InvalidString)
+}
+
+#[cold]
+fn not_self_describing() -> BufferError {
+    BufferError::InvalidData("the SSH encoding is not self-describing")
+}
+
+impl<'de, I: Input<'de>> de::Deserializer<'de> for &mut Deserializer<'_, I> {
+    type Error = BufferError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(not_self_describing())
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.input.get_bool()?)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.input.get_u32()? as i32;
+        visitor.visit_i8(i8::try_from(v).map_err(|_| out_of_range())?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.input.get_u32()? as i32;
+        visitor.visit_i16(i16::try_from(v).map_err(|_| out_of_range())?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.input.get_u32()? as i32)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.input.get_u64()? as i64)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.input.get_u8()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.input.get_u32()?;
+        visitor.visit_u16(u16::try_from(v).map_err(|_| out_of_range())?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.input.get_u32()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.input.get_u64()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(BufferError::InvalidData("floats have no SSH encoding"))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(BufferError::InvalidData("floats have no SSH encoding"))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.input.get_u32()?;
+        visitor.visit_char(char::from_u32(v).ok_or(BufferError::InvalidData("invalid char"))?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.input.get_string_ref()? {
+            Reference::Borrowed(bytes) => visitor.visit_borrowed_str(utf8(bytes)?),
+            Reference::Copied(bytes) => visitor.visit_str(utf8(bytes)?),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.input.get_string_ref()? {
+            Reference::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Reference::Copied(bytes) => visitor.visit_bytes(bytes),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.input.get_bool()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let remaining = self.count()?;
+        visitor.visit_seq(Counted { de: self, remaining })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Counted { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let remaining = self.count()?;
+        visitor.visit_map(Counted { de: self, remaining })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(not_self_describing())
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(not_self_describing())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// A sequence, tuple, struct or map with a known number of elements.
+struct Counted<'b, 'a, I> {
+    de: &'b mut Deserializer<'a, I>,
+    remaining: usize,
+}
+
+impl<'de, I: Input<'de>> de::SeqAccess<'de> for Counted<'_, '_, I> {
+    type Error = BufferError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, I: Input<'de>> de::MapAccess<'de> for Counted<'_, '_, I> {
+    type Error = BufferError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, I: Input<'de>> de::EnumAccess<'de> for &mut Deserializer<'_, I> {
+    type Error = BufferError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let index = u32::from(self.input.get_u8()?);
+        let value = seed.deserialize(IntoDeserializer::<BufferError>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, I: Input<'de>> de::VariantAccess<'de> for &mut Deserializer<'_, I> {
+    type Error = BufferError;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,278 @@
+// src/ssh/mod.rs
+//! Serde support for the SSH binary encoding (RFC 4251 §5)
+//!
+//! [`to_buffer`] and [`from_buffer`] convert any `Serialize`/`Deserialize`
+//! type to and from a [`Buffer`]; [`from_bytes`] deserializes from a byte
+//! slice and can borrow `&str` and `&[u8]` fields from it without copying.
+//!
+//! | Rust type | SSH encoding |
+//! |-----------|--------------|
+//! | `bool` | `boolean` (one byte) |
+//! | `u8` | `byte` |
+//! | `u16`, `u32`, `char` | `uint32` |
+//! | `u64` | `uint64` |
+//! | `i8`, `i16`, `i32` / `i64` | two's complement `uint32` / `uint64` |
+//! | `&str`, `String`, `&[u8]` | `string` |
+//! | `Vec<T>`, `[T]`, sets | `uint32` count, then each element |
+//! | maps | `uint32` count, then each key and value |
+//! | `Option<T>` | `boolean`, then the value if `true` |
+//! | structs, tuples | fields in order, no framing |
+//! | enums | variant index as a `byte`, then the fields |
+//!
+//! Because `u8` is a single byte, a `Vec<u8>` is count-prefixed bytes — the
+//! same as an SSH `string`.  Floats, `u128`/`i128` and self-describing
+//! operations (`deserialize_any`, untagged enums, skipped fields) are not
+//! supported.  Strings are subject to the SSH string limit (400 000 bytes),
+//! and so are the counts of sequences and maps, `Vec<u8>` included.
+//!
+//! # Examples
+//!
+//! ```
+//! use secbuf::{ssh, Buffer};
+//! use serde::{Deserialize, Serialize};
+//! # use secbuf::BufferError;
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct ChannelOpen<'a> {
+//!     msg_type: u8,
+//!     channel_type: &'a str,
+//!     sender_channel: u32,
+//!     initial_window_size: u32,
+//!     maximum_packet_size: u32,
+//! }
+//!
+//! let open = ChannelOpen {
+//!     msg_type: 90, // SSH_MSG_CHANNEL_OPEN
+//!     channel_type: "session",
+//!     sender_channel: 0,
+//!     initial_window_size: 2 * 1024 * 1024,
+//!     maximum_packet_size: 32768,
+//! };
+//! let mut buf = Buffer::new(64);
+//! ssh::to_buffer(&open, &mut buf)?;
+//!
+//! // Borrows `channel_type` straight out of the buffer's bytes.
+//! let parsed: ChannelOpen<'_> = ssh::from_bytes(buf.as_slice())?;
+//! assert_eq!(parsed, open);
+//! # Ok::<(), BufferError>(())
+//! ```
+
+mod de;
+mod ser;
+
+use crate::buffer::Buffer;
+use crate::error::{BufferError, Result};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Serializes `value` at the buffer's current position.
+///
+/// # Errors
+///
+/// Returns the first error raised while writing, e.g.
+/// [`BufferError::BufferOverflow`] or [`BufferError::InvalidString`].  The
+/// buffer is rolled back like a [`Checkpoint`](crate::Checkpoint), so a
+/// failed serialization leaves nothing behind.
+pub fn to_buffer<T: Serialize + ?Sized>(value: &T, buf: &mut Buffer) -> Result<()> {
+    let mut cp = buf.checkpoint();
+    value.serialize(&mut ser::Serializer::new(&mut cp))?;
+    cp.commit();
+    Ok(())
+}
+
+/// Deserializes a `T` from the buffer's current position and advances past
+/// it.
+///
+/// Strings are copied out of the buffer; use [`from_bytes`] to borrow them.
+///
+/// # Errors
+///
+/// Returns [`BufferError::BufferOverflow`] if the data is truncated,
+/// [`BufferError::InvalidString`] for over-long or non-UTF-8 strings, and
+/// [`BufferError::InvalidData`] or [`BufferError::Other`] if the data does
+/// not match `T`.  The position is restored on error.
+pub fn from_buffer<T: DeserializeOwned>(buf: &mut Buffer) -> Result<T> {
+    let mut cp = buf.checkpoint();
+    let value = T::deserialize(&mut de::Deserializer::new(&mut *cp))?;
+    cp.commit();
+    Ok(value)
+}
+
+/// Deserializes a `T` that fills `input` exactly, borrowing `&str` and
+/// `&[u8]` fields from it.
+///
+/// # Errors
+///
+/// As [`from_buffer`], plus [`BufferError::InvalidData`] if bytes remain
+/// after the value.
+pub fn from_bytes<'de, T: Deserialize<'de>>(mut input: &'de [u8]) -> Result<T> {
+    let value = T::deserialize(&mut de::Deserializer::new(&mut input))?;
+    if !input.is_empty() {
+        return Err(BufferError::InvalidData("trailing data after value"));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct KexInit {
+        msg_type: u8,
+        cookie: [u8; 16],
+        kex_algorithms: Vec<String>,
+        first_kex_packet_follows: bool,
+        reserved: u32,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Request {
+        Exit,
+        Signal(String),
+        WindowChange { cols: u32, rows: u32 },
+        Pair(u16, i64),
+    }
+
+    fn round_trip<T>(value: &T) -> Buffer
+    where
+        T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug,
+    {
+        let mut buf = Buffer::new(256);
+        to_buffer(value, &mut buf).unwrap();
+        buf.set_pos(0).unwrap();
+        assert_eq!(&from_buffer::<T>(&mut buf).unwrap(), value);
+        assert_eq!(buf.remaining(), 0);
+        assert_eq!(&from_bytes::<T>(buf.as_slice()).unwrap(), value);
+        buf
+    }
+
+    #[test]
+    fn test_struct_layout() {
+        let msg = KexInit {
+            msg_type: 20,
+            cookie: [0xAB; 16],
+            kex_algorithms: vec!["curve25519-sha256".into()],
+            first_kex_packet_follows: true,
+            reserved: 0,
+        };
+        let buf = round_trip(&msg);
+
+        let mut expected = Buffer::new(256);
+        expected.put_u8(20).unwrap();
+        expected.put_bytes(&[0xAB; 16]).unwrap();
+        expected.put_u32(1).unwrap();
+        expected.put_string(b"curve25519-sha256").unwrap();
+        expected.put_bool(true).unwrap();
+        expected.put_u32(0).unwrap();
+        assert_eq!(buf.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_byte_vec_is_ssh_string() {
+        let buf = round_trip(&b"payload".to_vec());
+        assert_eq!(buf.as_slice(), b"\0\0\0\x07payload");
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(round_trip(&0x1234u16).as_slice(), &[0, 0, 0x12, 0x34]);
+        assert_eq!(round_trip(&-2i32).as_slice(), &[0xFF, 0xFF, 0xFF, 0xFE]);
+        assert_eq!(round_trip(&-1i8).as_slice(), &[0xFF; 4]);
+        assert_eq!(round_trip(&u64::MAX).as_slice(), &[0xFF; 8]);
+        assert_eq!(round_trip(&'é').as_slice(), &[0, 0, 0, 0xE9]);
+        assert_eq!(round_trip(&Some(7u8)).as_slice(), &[1, 7]);
+        assert_eq!(round_trip(&None::<u8>).as_slice(), &[0]);
+        round_trip(&());
+    }
+
+    #[test]
+    fn test_enums_and_maps() {
+        assert_eq!(round_trip(&Request::Exit).as_slice(), &[0]);
+        round_trip(&Request::Signal("TERM".into()));
+        round_trip(&Request::WindowChange { cols: 80, rows: 24 });
+        round_trip(&Request::Pair(3, -9));
+
+        let map = BTreeMap::from([
+            (String::from("LANG"), String::from("C")),
+            ("TERM".into(), "xterm".into()),
+        ]);
+        round_trip(&map);
+    }
+
+    #[test]
+    fn test_borrowed_deserialization() {
+        #[derive(Deserialize)]
+        struct Borrowed<'a> {
+            name: &'a str,
+            #[serde(borrow)]
+            blob: &'a [u8],
+        }
+
+        let mut buf = Buffer::new(32);
+        buf.put_string(b"alice").unwrap();
+        buf.put_string(&[1, 2, 3]).unwrap();
+        let input = buf.as_slice();
+        let parsed: Borrowed<'_> = from_bytes(input).unwrap();
+        assert_eq!(parsed.name, "alice");
+        assert_eq!(parsed.blob, &[1, 2, 3]);
+        assert!(core::ptr::eq(parsed.blob.as_ptr(), input[13..].as_ptr()));
+
+        assert_eq!(
+            from_bytes::<&str>(&input[..12]),
+            Err(BufferError::InvalidData("trailing data after value"))
+        );
+    }
+
+    #[test]
+    fn test_errors_roll_back() {
+        let mut buf = Buffer::new(8);
+        buf.put_u8(0xEE).unwrap();
+        assert!(to_buffer(&(1u32, 2u64), &mut buf).is_err());
+        assert_eq!(buf.as_slice(), &[0xEE]);
+
+        buf.set_pos(0).unwrap();
+        assert!(from_buffer::<(u8, u32)>(&mut buf).is_err());
+        assert_eq!(buf.pos(), 0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        // u16 out of range
+        assert_eq!(
+            from_bytes::<u16>(&[0, 1, 0, 0]),
+            Err(BufferError::InvalidData("integer out of range"))
+        );
+        // invalid char
+        assert_eq!(
+            from_bytes::<char>(&[0, 0, 0xD8, 0]),
+            Err(BufferError::InvalidData("invalid char"))
+        );
+        // string over the SSH limit
+        assert_eq!(from_bytes::<String>(&[0, 0x10, 0, 0]), Err(BufferError::InvalidString));
+        // element count larger than the remaining input
+        assert_eq!(
+            from_bytes::<Vec<u32>>(&[0, 0, 0, 5, 0]),
+            Err(BufferError::InvalidData("sequence count exceeds remaining data"))
+        );
+        // count over the SSH string limit
+        assert_eq!(
+            from_bytes::<Vec<u8>>(&[0, 0x10, 0, 0]),
+            Err(BufferError::InvalidData("sequence count exceeds the SSH string limit"))
+        );
+        // unknown variant index
+        assert!(matches!(from_bytes::<Request>(&[9]), Err(BufferError::Other(_))));
+        // floats have no SSH encoding
+        let mut buf = Buffer::new(8);
+        assert!(to_buffer(&1.5f32, &mut buf).is_err());
+        // a byte vector over the SSH string limit
+        assert_eq!(
+            to_buffer(&vec![0u8; 500_000], &mut buf),
+            Err(BufferError::InvalidData("sequence count exceeds the SSH string limit"))
+        );
+        assert!(buf.is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+// src/ssh/ser.rs
+//! SSH binary [`Serializer`](serde::Serializer) writing to a [`Buffer`]
+
+use crate::buffer::Buffer;
+use crate::buffer::ops::MAX_STRING_LEN;
+use crate::error::{BufferError, Result};
+use serde::ser::{self, Serialize};
+
+/// Writes serde values to a [`Buffer`] in the SSH encoding.
+pub(super) struct Serializer<'a> {
+    buf: &'a mut Buffer,
+}
+
+impl<'a> Serializer<'a> {
+    pub(super) fn new(buf: &'a mut Buffer) -> Self {
+        Self { buf }
+    }
+
+    /// Writes the discriminant byte of an enum variant.
+    fn variant(&mut self, index: u32) -> Result<()> {
+        let tag = u8::try_from(index)
+            .map_err(|_| BufferError::InvalidData("enum variant index does not fit in a byte"))?;
+        self.buf.put_u8(tag)
+    }
+
+    /// Writes the count prefix of a sequence or map, checking it against
+    /// [`MAX_STRING_LEN`] so that a `Vec<u8>` obeys the same limit as a
+    /// `string`.
+    fn count(&mut self, len: Option<usize>) -> Result<()> {
+        let len = len.ok_or(BufferError::InvalidData("sequence length must be known"))?;
+        if len > MAX_STRING_LEN {
+            return Err(BufferError::InvalidData("sequence count exceeds the SSH string limit"));
+        }
+        self.buf.put_u32(len as u32)
+    }
+}
+
+impl ser::Serializer for &mut Serializer<'_> {
+    type Ok = ();
+    type Error = BufferError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.buf.put_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.buf.put_u32(v as u32)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.buf.put_u64(v as u64)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.buf.put_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.buf.put_u32(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.buf.put_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.buf.put_u64(v)
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<()> {
+        Err(BufferError::InvalidData("floats have no SSH encoding"))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<()> {
+        Err(BufferError::InvalidData("floats have no SSH encoding"))
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.buf.put_u32(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.buf.put_string(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.buf.put_string(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.buf.put_bool(false)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        self.buf.put_bool(true)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.variant(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.variant(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.count(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.variant(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.count(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.variant(variant_index)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Implements a compound serializer trait whose elements are written back
+/// to back.
+macro_rules! impl_compound {
+    ($($trait:ident :: $method:ident ($($name:ident: $ty:ty),*);)*) => {
+        $(
+            impl ser::$trait for &mut Serializer<'_> {
+                type Ok = ();
+                type Error = BufferError;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, $($name: $ty,)* value: &T) -> Result<()> {
+                    $(let _ = $name;)*
+                    value.serialize(&mut **self)
+                }
+
+                fn end(self) -> Result<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_compound! {
+    SerializeSeq::serialize_element();
+    SerializeTuple::serialize_element();
+    SerializeTupleStruct::serialize_field();
+    SerializeTupleVariant::serialize_field();
+    SerializeStruct::serialize_field(key: &'static str);
+    SerializeStructVariant::serialize_field(key: &'static str);
+}
+
+impl ser::SerializeMap for &mut Serializer<'_> {
+    type Ok = ();
+    type Error = BufferError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
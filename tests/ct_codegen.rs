@@ -0,0 +1,80 @@
+// tests/ct_codegen.rs
+//! Checks that the constant-time helpers compile to branch-free x86_64 code.
+//!
+//! The library is rebuilt in release mode with `--cfg secbuf_ct_probe`,
+//! which adds `#[no_mangle]` fixed-size instantiations of the `secbuf::ct`
+//! functions, and the emitted assembly of each probe is scanned for
+//! conditional jumps.  With the input length fixed at compile time, any
+//! conditional jump left in a probe can only depend on the secret data.
+//!
+//! Ignored by default because it runs a nested `cargo` build; CI runs it on
+//! its x86_64 runner with:
+//!
+//! ```text
+//! cargo test --release --test ct_codegen -- --ignored
+//! ```
+#![cfg(target_arch = "x86_64")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+const PROBES: &[&str] = &[
+    "secbuf_ct_probe_eq",
+    "secbuf_ct_probe_is_zero",
+    "secbuf_ct_probe_select",
+];
+
+fn emit_probe_asm() -> String {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("ct-codegen");
+    let asm_path = target_dir.join("secbuf.s");
+
+    let status = Command::new(env!("CARGO"))
+        .current_dir(&manifest_dir)
+        .args(["rustc", "--lib", "--release", "--target-dir"])
+        .arg(&target_dir)
+        .args(["--", "--cfg", "secbuf_ct_probe", "-C", "codegen-units=1"])
+        .arg(format!("--emit=asm={}", asm_path.display()))
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "release build with probes failed");
+
+    std::fs::read_to_string(&asm_path).expect("assembly output missing")
+}
+
+/// Returns the instructions between `name:` and the end of the function.
+fn function_body<'a>(asm: &'a str, name: &str) -> Vec<&'a str> {
+    let label = format!("{name}:");
+    asm.lines()
+        .skip_while(|line| line.trim() != label)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with(".Lfunc_end"))
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('.') && !line.starts_with('#'))
+        .collect()
+}
+
+fn is_conditional_jump(insn: &str) -> bool {
+    let mnemonic = insn.split_whitespace().next().unwrap_or("");
+    mnemonic.starts_with('j') && mnemonic != "jmp"
+}
+
+#[test]
+#[ignore = "runs a nested release build; use --ignored"]
+fn ct_probes_have_no_conditional_branches() {
+    let asm = emit_probe_asm();
+    for probe in PROBES {
+        let body = function_body(&asm, probe);
+        assert!(!body.is_empty(), "probe {probe} not found in assembly");
+
+        let branches: Vec<_> = body
+            .iter()
+            .filter(|insn| is_conditional_jump(insn))
+            .collect();
+        assert!(
+            branches.is_empty(),
+            "{probe} contains conditional branches: {branches:?}\n{}",
+            body.join("\n")
+        );
+    }
+}
@@ -0,0 +1,93 @@
+// tests/guarded_memory.rs
+//! Death tests for `AllocMode::Guarded` and `SealedBuffer`.
+//!
+//! Each test re-runs this binary filtered to a single "victim" test with
+//! `SECBUF_DEATH_TEST` set, and asserts that the child was killed by a
+//! signal rather than exiting normally.
+#![cfg(all(unix, feature = "std"))]
+
+use secbuf::prelude::*;
+use std::os::unix::process::ExitStatusExt;
+use std::process::Command;
+
+const VICTIM_ENV: &str = "SECBUF_DEATH_TEST";
+
+/// Runs the named victim test in a child process and returns the signal
+/// that terminated it, if any.
+fn run_victim(name: &str) -> Option<i32> {
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args([name, "--exact", "--nocapture", "--test-threads=1"])
+        .env(VICTIM_ENV, "1")
+        .status()
+        .unwrap();
+    status.signal()
+}
+
+fn is_victim() -> bool {
+    std::env::var_os(VICTIM_ENV).is_some()
+}
+
+#[test]
+fn victim_overflow() {
+    if !is_victim() {
+        return;
+    }
+    let mut buf = Buffer::guarded(100).unwrap();
+    buf.set_len(100).unwrap();
+    let end = unsafe { buf.as_mut_slice().as_mut_ptr().add(100) };
+    // The first byte past the buffer is on the trailing guard page.
+    unsafe { std::ptr::write_volatile(end, 0x41) };
+}
+
+#[test]
+fn victim_underflow() {
+    if !is_victim() {
+        return;
+    }
+    let mut buf = Buffer::guarded(100).unwrap();
+    buf.set_len(100).unwrap();
+    let before = unsafe { buf.as_mut_slice().as_mut_ptr().sub(1) };
+    // Clobbers the last canary byte; detected when `buf` drops.
+    unsafe { std::ptr::write_volatile(before, !std::ptr::read_volatile(before)) };
+}
+
+#[test]
+fn victim_sealed_read() {
+    if !is_victim() {
+        return;
+    }
+    let sealed = SealedBuffer::from_slice(b"host key").unwrap();
+    let ptr = sealed.with_read(|bytes| bytes.as_ptr()).unwrap();
+    // The window has closed; the page is PROT_NONE again.
+    let _ = unsafe { std::ptr::read_volatile(ptr) };
+}
+
+#[test]
+fn test_overflow_hits_guard_page() {
+    let signal = run_victim("victim_overflow");
+    assert!(
+        matches!(signal, Some(libc::SIGSEGV) | Some(libc::SIGBUS)),
+        "expected a memory fault, got {signal:?}"
+    );
+}
+
+#[test]
+fn test_corrupted_canary_aborts() {
+    assert_eq!(run_victim("victim_underflow"), Some(libc::SIGABRT));
+}
+
+#[test]
+fn test_sealed_buffer_faults_outside_window() {
+    let signal = run_victim("victim_sealed_read");
+    assert!(
+        matches!(signal, Some(libc::SIGSEGV) | Some(libc::SIGBUS)),
+        "expected a memory fault, got {signal:?}"
+    );
+}
+
+#[test]
+fn test_intact_buffer_drops_cleanly() {
+    let mut buf = Buffer::guarded(32).unwrap();
+    buf.put_bytes(&[0xFF; 32]).unwrap();
+    drop(buf);
+}
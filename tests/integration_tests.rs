@@ -1,5 +1,6 @@
 // tests/integration_tests.rs
 //! Integration tests for the buffer module
+#![cfg(feature = "std")]
 
 use secbuf::prelude::*;
 
@@ -39,6 +40,7 @@ fn test_buffer_pool_concurrency() {
         buffer_size: 1024,
         max_pool_size: 100,
         min_pool_size: 10,
+        ..PoolConfig::default()
     }));
 
     let mut handles = vec![];
@@ -112,12 +114,34 @@ fn test_large_string_operations() {
     assert_eq!(retrieved, large_string);
 }
 
+#[test]
+fn test_zero_copy_string_parsing() {
+    // SSH_MSG_USERAUTH_REQUEST: user, service, method, (skipped) payload
+    let mut packet = Buffer::new(256);
+    packet.put_byte(50).unwrap();
+    packet.put_string(b"alice").unwrap();
+    packet.put_string(b"ssh-connection").unwrap();
+    packet.put_string(b"password").unwrap();
+    packet.put_string(b"hunter2").unwrap();
+    packet.put_string(&[0xff, 0xfe]).unwrap();
+
+    packet.set_pos(0).unwrap();
+    assert_eq!(packet.get_byte().unwrap(), 50);
+    assert_eq!(packet.get_string_ref().unwrap(), b"alice");
+    assert_eq!(packet.get_str_ref().unwrap(), "ssh-connection");
+    assert_eq!(packet.get_str_ref().unwrap(), "password");
+    packet.eat_string().unwrap();
+    assert_eq!(packet.get_str_ref(), Err(BufferError::InvalidString));
+    assert_eq!(packet.remaining(), 0);
+}
+
 #[test]
 fn test_pool_statistics_accuracy() {
     let pool = BufferPool::new(PoolConfig {
         buffer_size: 512,
         max_pool_size: 20,
         min_pool_size: 5,
+        ..PoolConfig::default()
     });
 
     let initial_stats = pool.stats();
@@ -158,9 +182,9 @@ fn test_buffer_error_handling() {
     let mut buf = Buffer::new(10);
 
     assert!(buf.set_pos(100).is_err());
-    assert!(buf.put_bytes(&vec![0; 20]).is_err());
+    assert!(buf.put_bytes(&[0; 20]).is_err());
 
-    buf.put_bytes(&vec![0; 5]).unwrap();
+    buf.put_bytes(&[0; 5]).unwrap();
     buf.set_pos(0).unwrap();
     assert!(buf.get_bytes(10).is_err());
 }
@@ -207,6 +231,7 @@ fn test_pool_max_size_enforcement() {
         buffer_size: 256,
         max_pool_size: 5,
         min_pool_size: 2,
+        ..PoolConfig::default()
     });
 
     for _ in 0..20 {
@@ -220,7 +245,7 @@ fn test_pool_max_size_enforcement() {
 #[test]
 fn test_buffer_remaining() {
     let mut buf = Buffer::new(100);
-    buf.put_bytes(&vec![0; 50]).unwrap();
+    buf.put_bytes(&[0; 50]).unwrap();
 
     buf.set_pos(0).unwrap();
     assert_eq!(buf.remaining(), 50);
@@ -248,16 +273,16 @@ fn test_bool_operations() {
     buf.put_byte(255).unwrap();
 
     buf.set_pos(0).unwrap();
-    assert_eq!(buf.get_bool().unwrap(), true);
-    assert_eq!(buf.get_bool().unwrap(), false);
-    assert_eq!(buf.get_bool().unwrap(), true);
+    assert!(buf.get_bool().unwrap());
+    assert!(!buf.get_bool().unwrap());
+    assert!(buf.get_bool().unwrap());
 }
 
 #[test]
 fn test_circular_buffer_full_cycle() {
     let mut cbuf = CircularBuffer::new(100);
 
-    cbuf.write(&vec![b'A'; 100]).unwrap();
+    cbuf.write(&[b'A'; 100]).unwrap();
     assert!(cbuf.is_full());
     assert_eq!(cbuf.available(), 0);
 
@@ -297,6 +322,7 @@ fn test_fast_pool_concurrency() {
         buffer_size: 1024,
         max_pool_size: 100,
         min_pool_size: 10,
+        ..PoolConfig::default()
     }));
 
     let handles: Vec<_> = (0..4)
@@ -317,4 +343,39 @@ fn test_fast_pool_concurrency() {
 
     let stats = pool.stats();
     assert_eq!(stats.acquired, 400);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_frozen_packet_fan_out() {
+    use std::thread;
+
+    let pool = FastBufferPool::new(PoolConfig {
+        buffer_size: 256,
+        max_pool_size: 4,
+        min_pool_size: 0,
+        ..PoolConfig::default()
+    });
+
+    let mut buf = pool.acquire();
+    buf.put_string(b"channel data").unwrap();
+    let packet = buf.freeze();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let mut view = packet.clone();
+            thread::spawn(move || {
+                let len = view.split_to(4).unwrap();
+                assert_eq!(&len[..], &[0, 0, 0, 12]);
+                assert_eq!(&view[..], b"channel data");
+            })
+        })
+        .collect();
+
+    for h in handles {
+        h.join().unwrap();
+    }
+
+    assert_eq!(packet.ref_count(), 1);
+    drop(packet);
+    assert_eq!(pool.stats().returned, 1);
+}
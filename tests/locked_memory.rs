@@ -0,0 +1,96 @@
+// tests/locked_memory.rs
+//! `LockedBuffer` behaviour under a restricted `RLIMIT_MEMLOCK`.
+//!
+//! Runs in its own test binary because lowering the limit affects the whole
+//! process.
+#![cfg(all(unix, feature = "std"))]
+
+use secbuf::prelude::*;
+use std::sync::Mutex;
+
+/// Serializes the tests that change the process-wide limit.
+static RLIMIT_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_memlock_limit_is_reported() {
+    let _guard = RLIMIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let one_page = LockedBuffer::new(1).unwrap();
+    let mut old = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    unsafe {
+        assert_eq!(libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut old), 0);
+        let zero = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: old.rlim_max,
+        };
+        if libc::setrlimit(libc::RLIMIT_MEMLOCK, &zero) != 0 {
+            return;
+        }
+    }
+
+    let result = LockedBuffer::new(1 << 20);
+    unsafe { libc::setrlimit(libc::RLIMIT_MEMLOCK, &old) };
+
+    match result {
+        Err(BufferError::MemlockLimitExceeded { requested, limit }) => {
+            assert_eq!(requested, 1 << 20);
+            assert_eq!(limit, 0);
+            let msg = BufferError::MemlockLimitExceeded { requested, limit }.to_string();
+            assert!(msg.contains("RLIMIT_MEMLOCK"));
+        }
+        // Processes with CAP_IPC_LOCK are not subject to the limit.
+        Ok(buf) => assert!(buf.is_locked()),
+        Err(e) => panic!("unexpected error: {e}"),
+    }
+
+    // Existing locked buffers are unaffected.
+    assert!(one_page.is_locked());
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_buf_mut_panics_when_locked_growth_fails() {
+    use bytes::BufMut;
+    use std::panic::{self, AssertUnwindSafe};
+
+    let _guard = RLIMIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut buf = LockedBuffer::with_capacity(16).unwrap();
+    buf.set_growth_policy(GrowthPolicy::Doubling { max: 1 << 20 })
+        .unwrap();
+    buf.put_bytes(&[0x11; 16]).unwrap();
+    assert!(buf.remaining_mut() > 0);
+
+    let mut old = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    unsafe {
+        assert_eq!(libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut old), 0);
+        let zero = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: old.rlim_max,
+        };
+        if libc::setrlimit(libc::RLIMIT_MEMLOCK, &zero) != 0 {
+            return;
+        }
+    }
+
+    // `put_slice` must not spin on empty chunks while `remaining_mut` is
+    // non-zero.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (*buf).put_slice(&[0x22; 64 * 1024]);
+    }));
+    unsafe { libc::setrlimit(libc::RLIMIT_MEMLOCK, &old) };
+
+    match result {
+        Err(payload) => {
+            let msg = payload.downcast_ref::<String>().unwrap();
+            assert!(msg.contains("cannot grow Buffer storage"), "{msg}");
+        }
+        // Processes with CAP_IPC_LOCK are not subject to the limit.
+        Ok(()) => assert_eq!(buf.len(), 16 + 64 * 1024),
+    }
+    assert!(buf.is_locked());
+}
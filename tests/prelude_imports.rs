@@ -0,0 +1,22 @@
+// tests/prelude_imports.rs
+//! The prelude must not conflict with `bytes::Buf`/`BufMut`, which are
+//! implemented for `CircularBuffer` and byte slices.
+#![cfg(feature = "bytes")]
+
+use bytes::{Buf, BufMut};
+use secbuf::prelude::*;
+
+#[test]
+fn test_prelude_with_bytes_traits() {
+    let mut slice: &[u8] = &[0, 0, 0, 7];
+    assert_eq!(slice.get_u32(), 7);
+
+    let mut out = [0u8; 4];
+    let mut cursor = &mut out[..];
+    cursor.put_u32(9);
+    assert_eq!(out, [0, 0, 0, 9]);
+
+    let mut ring = CircularBuffer::new(8);
+    ring.write(&out).unwrap();
+    assert_eq!(ring.get_u32(), 9);
+}
@@ -0,0 +1,90 @@
+// tests/zeroize_realloc.rs
+//! Verifies that no capacity change on `Buffer` ever frees a block that still
+//! holds data.
+//!
+//! An instrumented global allocator inspects every block as it is freed on the
+//! test thread while tracking is enabled, and counts those containing any
+//! nonzero byte.  Tracking is thread-local so the harness's own allocations on
+//! other threads are ignored.
+
+use secbuf::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CheckingAllocator;
+
+static DIRTY_FREES: AtomicUsize = AtomicUsize::new(0);
+static TRACKED_FREES: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for CheckingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if TRACKING.try_with(Cell::get).unwrap_or(false) {
+            let block = unsafe { std::slice::from_raw_parts(ptr, layout.size()) };
+            TRACKED_FREES.fetch_add(1, Ordering::Relaxed);
+            if block.iter().any(|&b| b != 0) {
+                DIRTY_FREES.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        unsafe { System.dealloc(ptr, layout) }
+    }
+    // `realloc` is deliberately not overridden: the default goes through
+    // `alloc` + copy + `dealloc`, so every moved block is inspected.
+}
+
+#[global_allocator]
+static ALLOC: CheckingAllocator = CheckingAllocator;
+
+/// Runs `f` with tracking enabled and returns (dirty frees, tracked frees).
+fn tracked(f: impl FnOnce()) -> (usize, usize) {
+    DIRTY_FREES.store(0, Ordering::Relaxed);
+    TRACKED_FREES.store(0, Ordering::Relaxed);
+    TRACKING.with(|t| t.set(true));
+    f();
+    TRACKING.with(|t| t.set(false));
+    (
+        DIRTY_FREES.load(Ordering::Relaxed),
+        TRACKED_FREES.load(Ordering::Relaxed),
+    )
+}
+
+#[test]
+fn test_capacity_changes_never_free_dirty_blocks() {
+    let (dirty, freed) = tracked(|| {
+        let mut buf = Buffer::new(16);
+        buf.put_bytes(&[0xA5; 16]).unwrap();
+
+        buf.resize(64).unwrap();
+        buf.reserve(1000);
+        buf.set_len(2000).unwrap();
+        buf.incr_len(4000).unwrap();
+        buf.set_pos(buf.len()).unwrap();
+        buf.incr_write_pos(8000).unwrap();
+        buf.resize(32).unwrap();
+        buf.shrink_to_fit();
+        drop(buf);
+
+        let mut grown = Buffer::growable(8, 1 << 16);
+        for i in 0..4096u32 {
+            grown.put_u32(i | 0x8080_8080).unwrap();
+        }
+        drop(grown);
+
+        let mut with_cap = Buffer::with_capacity(8);
+        with_cap.set_len(8).unwrap();
+        with_cap.as_mut_slice().fill(0xFF);
+        with_cap.incr_len(100).unwrap();
+        drop(with_cap);
+    });
+
+    assert!(freed >= 8, "expected reallocations to be observed, saw {freed}");
+    assert_eq!(dirty, 0, "{dirty} of {freed} freed blocks still held data");
+}